@@ -36,6 +36,8 @@ impl BackendState {
     async fn start(self, recv: BackendReceiver) {
         log::info!("Starting backend");
 
+        self.send_usage_history();
+
         self.handle(recv).await;
     }
 
@@ -54,3 +56,23 @@ impl BackendState {
         }
     }
 }
+
+#[cfg(test)]
+impl BackendState {
+    /// State exporting into an empty temporary folder, with the frontend end of its channel.
+    pub(crate) fn for_test(name: &str) -> (Self, bridge::handle::FrontendReceiver) {
+        let (_, self_handle, recv, send) = bridge::handle::create_pair();
+
+        let export_dir =
+            std::env::temp_dir().join(format!("bingo-gen-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&export_dir);
+        std::fs::create_dir_all(&export_dir).unwrap();
+
+        let state = BackendState {
+            self_handle,
+            send,
+            export_dir: Arc::new(export_dir),
+        };
+        (state, recv)
+    }
+}
@@ -1,22 +1,275 @@
-use bridge::message::MessageToBackend;
+use bridge::message::{MessageToBackend, MessageToFrontend};
 
-use crate::backend::BackendState;
+use crate::{backend::BackendState, caller, generator, metadata};
 
 impl BackendState {
     pub async fn handle_message(&self, message: MessageToBackend) {
         match message {
-            MessageToBackend::CreateBingoSyncFile { data } => {
-                match self.export_bingo_sync(data).await {
+            MessageToBackend::CreateBingoSyncFile { data, metadata } => {
+                match self.export_bingo_sync(data, metadata).await {
                     Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
                     Err(e) => self.send.send_error(format!("Error: '{}'", e)),
                 }
             }
-            MessageToBackend::CreateLockoutLiveFile { data } => {
-                match self.export_lockout_live(data).await {
+            MessageToBackend::CreateLockoutLiveFile { data, metadata } => {
+                match self.export_lockout_live(data, metadata).await {
                     Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
                     Err(e) => self.send.send_error(format!("Error: '{}'", e)),
                 }
             }
+            MessageToBackend::CreateBoardImage { image, format } => {
+                match self.export_image(image, format).await {
+                    Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::CreateHtmlBoard { board, spoiler } => {
+                match self.export_html(board, spoiler).await {
+                    Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::CreateBingoSyncPoolFile { pool, filter, seed } => {
+                match self.export_bingo_sync_pool(pool, filter, seed).await {
+                    Ok((f, seed)) => self
+                        .send
+                        .send_success(format!("Created file '{}' with seed {}", f, seed)),
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::CreateLockoutLivePoolFile { pool, filter } => {
+                match self.export_lockout_live_pool(pool, filter).await {
+                    Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::LoadGoalPool { path } => match self.load_goal_pool(&path).await {
+                Ok(pool) => {
+                    self.send.send_success(format!(
+                        "Loaded {} goals from '{}'",
+                        pool.goals.len(),
+                        path.display()
+                    ));
+                    self.send.send(MessageToFrontend::GoalPoolLoaded {
+                        path: Some(path),
+                        pool: Box::new(pool),
+                    });
+                }
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::ImportPoolTable { path, mapping } => {
+                match self.import_pool_table(&path, &mapping).await {
+                    Ok(pool) => {
+                        self.send.send_success(format!(
+                            "Imported {} goals from '{}'",
+                            pool.goals.len(),
+                            path.display()
+                        ));
+                        self.send.send(MessageToFrontend::GoalPoolLoaded {
+                            path: None,
+                            pool: Box::new(pool),
+                        });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::ImportGoalList { path } => match self.import_goal_list(&path).await {
+                Ok(pool) => {
+                    self.send.send_success(format!(
+                        "Imported {} goals from '{}'",
+                        pool.goals.len(),
+                        path.display()
+                    ));
+                    self.send.send(MessageToFrontend::GoalPoolLoaded {
+                        path: None,
+                        pool: Box::new(pool),
+                    });
+                }
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::OpenProject { path } => match self.open_project(&path).await {
+                Ok(project) => {
+                    self.send
+                        .send_success(format!("Opened project '{}'", path.display()));
+                    self.send.send(MessageToFrontend::ProjectOpened {
+                        path,
+                        project: Box::new(project),
+                    });
+                }
+                Err(e) => self.send.send_error(format!("Error: '{:#}'", e)),
+            },
+            MessageToBackend::SaveProject { path, project } => {
+                match self.save_project(path, project).await {
+                    Ok(path) => {
+                        self.send
+                            .send_success(format!("Saved project '{}'", path.display()));
+                        self.send.send(MessageToFrontend::ProjectSaved { path });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::ImportBoardTable { path } => {
+                match self.import_board_table(&path).await {
+                    Ok((grid_size, cells)) => {
+                        self.send.send_success(format!(
+                            "Imported {}x{} board from '{}'",
+                            grid_size.side(),
+                            grid_size.side(),
+                            path.display()
+                        ));
+                        self.send
+                            .send(MessageToFrontend::BoardImported { grid_size, cells });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::CreatePoolTableFile {
+                pool,
+                filter,
+                delimiter,
+            } => match self.export_pool_table(pool, filter, delimiter).await {
+                Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::CreateBoardTableFile {
+                grid_size,
+                cells,
+                delimiter,
+            } => match self.export_board_table(grid_size, cells, delimiter).await {
+                Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::GenerateBoard { pool, options } => {
+                match generator::generate(&pool, &options) {
+                    Ok(board) => {
+                        self.send.send_success(format!(
+                            "Generated board with seed {}, line difficulty {}..{}",
+                            board.seed, board.line_spread.min, board.line_spread.max
+                        ));
+                        self.send.send(MessageToFrontend::BoardGenerated { board });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::RerollCells {
+                pool,
+                grid_size,
+                board,
+                cells,
+            } => match generator::reroll(&pool, grid_size, None, &board, &cells) {
+                Ok(board) => {
+                    self.send.send_success(format!(
+                        "Rerolled {} cells, line difficulty {}..{}",
+                        cells.len(),
+                        board.line_spread.min,
+                        board.line_spread.max
+                    ));
+                    self.send.send(MessageToFrontend::BoardGenerated { board });
+                }
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::VerifyExport { path } => match metadata::verify_export(&path) {
+                Ok(report) if report.differences.is_empty() => self.send.send_success(format!(
+                    "'{}' matches the regenerated board",
+                    report.export_file
+                )),
+                Ok(report) => {
+                    let version = report
+                        .other_version
+                        .map(|v| format!(" (exported with version {})", v))
+                        .unwrap_or_default();
+                    self.send.send_warning(format!(
+                        "'{}' differs from the regenerated board in {} places{}: {}",
+                        report.export_file,
+                        report.differences.len(),
+                        version,
+                        report.differences.join(", ")
+                    ));
+                }
+                Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+            },
+            MessageToBackend::BulkGenerate {
+                pool,
+                pool_path,
+                options,
+            } => {
+                let count = options.count;
+                match self.bulk_generate(pool, pool_path, options).await {
+                    Ok(folder) => self
+                        .send
+                        .send_success(format!("Generated {} boards into '{}'", count, folder)),
+                    Err(e) => self.send.send_error(format!("Error: '{:#}'", e)),
+                }
+                self.send.send(MessageToFrontend::BulkFinished);
+            }
+            MessageToBackend::CreatePdfCards { pool, options } => {
+                let count = options.count;
+                match self.export_pdf_cards(pool, options).await {
+                    Ok(f) => self
+                        .send
+                        .send_success(format!("Created {} cards in '{}'", count, f)),
+                    Err(e) => self.send.send_error(format!("Error: '{:#}'", e)),
+                }
+                self.send.send(MessageToFrontend::BulkFinished);
+            }
+            MessageToBackend::OpenCardSet { path } => match self.open_card_set(&path).await {
+                Ok((card_set, game)) => {
+                    self.send.send_success(format!(
+                        "Opened {} cards, {} of {} goals called",
+                        card_set.cards.len(),
+                        game.called().len(),
+                        game.order.len()
+                    ));
+                    self.send.send(MessageToFrontend::CallerGameLoaded {
+                        path,
+                        card_set: Box::new(card_set),
+                        game,
+                    });
+                }
+                Err(e) => self.send.send_error(format!("Error: '{:#}'", e)),
+            },
+            MessageToBackend::RestartCallerGame { path, seed } => {
+                match self.restart_caller_game(&path, seed).await {
+                    Ok((card_set, game)) => {
+                        self.send
+                            .send_success(format!("Started a new game with seed {}", game.seed));
+                        self.send.send(MessageToFrontend::CallerGameLoaded {
+                            path,
+                            card_set: Box::new(card_set),
+                            game,
+                        });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{:#}'", e)),
+                }
+            }
+            MessageToBackend::SaveCallerGame { path, game } => {
+                if let Err(e) = caller::save_game(&path, &game) {
+                    self.send.send_error(format!("Error: '{}'", e));
+                }
+            }
+            MessageToBackend::CreateCallerSheet { path } => {
+                match self.export_caller_sheet(&path).await {
+                    Ok(f) => self.send.send_success(format!("Created file '{}'", f)),
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+            }
+            MessageToBackend::PlanTournament { pool, options } => {
+                match self.plan_tournament(pool, options).await {
+                    Ok((folder, report)) => {
+                        self.send.send_success(format!(
+                            "Planned {} boards into '{}', difficulty {}..{}",
+                            report.boards.len(),
+                            folder,
+                            report.difficulty_min,
+                            report.difficulty_max
+                        ));
+                        self.send
+                            .send(MessageToFrontend::TournamentPlanned { report });
+                    }
+                    Err(e) => self.send.send_error(format!("Error: '{}'", e)),
+                }
+                self.send.send(MessageToFrontend::BulkFinished);
+            }
         };
     }
 }
@@ -0,0 +1,277 @@
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use bridge::{
+    board::{BulkOptions, DifficultyTarget, GenerateMode, GenerateOptions, GridSize},
+    card::{BingoSyncCard, ExportFormat, LockoutLiveBoard},
+    goal::GoalPool,
+    handle::FrontendHandle,
+    message::MessageToFrontend,
+};
+use chrono::Local;
+
+use crate::{backend::BackendState, generator};
+
+/// Time between two progress updates, one per board would flood the frontend channel.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+#[derive(serde::Serialize)]
+struct BulkIndex {
+    generator_version: String,
+    created_at: String,
+    pool_path: Option<PathBuf>,
+    grid_size: GridSize,
+    mode: GenerateMode,
+    line_difficulty: Option<DifficultyTarget>,
+    format: ExportFormat,
+    unique_goals: bool,
+    boards: Vec<BulkIndexEntry>,
+}
+
+#[derive(serde::Serialize)]
+struct BulkIndexEntry {
+    file: String,
+    seed: u64,
+    line_difficulty_min: usize,
+    line_difficulty_max: usize,
+    goals: Vec<String>,
+}
+
+impl BackendState {
+    /// Generates `options.count` boards into a new folder along with an `index.json` listing them,
+    /// returns the folder name.
+    pub async fn bulk_generate(
+        &self,
+        pool: GoalPool,
+        pool_path: Option<PathBuf>,
+        options: BulkOptions,
+    ) -> Result<String> {
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || state.write_bulk(pool, pool_path, options)).await?
+    }
+
+    fn write_bulk(
+        &self,
+        mut pool: GoalPool,
+        pool_path: Option<PathBuf>,
+        options: BulkOptions,
+    ) -> Result<String> {
+        let grid_size = options.generate.grid_size;
+
+        if options.count == 0 {
+            bail!("Bulk generation needs at least one board");
+        }
+        if options.format == ExportFormat::BingoSync && grid_size > GridSize::Size5 {
+            bail!("Bingosync boards can't be bigger than 5x5");
+        }
+
+        let date = Local::now();
+        let folder = format!("{}_bulk", date.format("%Y-%m-%d_%H-%M-%S"));
+        let dir = self.export_dir.join(&folder);
+        std::fs::create_dir_all(&dir)?;
+
+        let first_seed = options.first_seed.unwrap_or_else(rand::random);
+        let mut progress = Progress::new(self.send.clone(), options.count);
+        let mut boards = Vec::with_capacity(options.count);
+        for idx in 0..options.count {
+            let seed = first_seed.wrapping_add(idx as u64);
+            let board = generator::generate(
+                &pool,
+                &GenerateOptions {
+                    seed: Some(seed),
+                    pinned: Default::default(),
+                    ..options.generate.clone()
+                },
+            )
+            .with_context(|| format!("Board {} with seed {}", idx + 1, seed))?;
+
+            let goals: Vec<String> = board
+                .goals
+                .iter()
+                .map(|g| g.name.trim().to_owned())
+                .collect();
+            let file = format!("board_{:03}.json", idx + 1);
+            write_board(
+                &dir.join(&file),
+                options.format,
+                &pool.game_name,
+                grid_size,
+                &goals,
+            )?;
+
+            if options.unique_goals {
+                pool.goals.retain(|g| {
+                    board
+                        .goals
+                        .iter()
+                        .all(|used| used.pool_name() != g.name.trim())
+                });
+            }
+
+            boards.push(BulkIndexEntry {
+                file,
+                seed,
+                line_difficulty_min: board.line_spread.min,
+                line_difficulty_max: board.line_spread.max,
+                goals,
+            });
+
+            progress.update(idx + 1);
+        }
+
+        let index = BulkIndex {
+            generator_version: env!("CARGO_PKG_VERSION").to_owned(),
+            created_at: date.to_rfc3339(),
+            pool_path,
+            grid_size,
+            mode: options.generate.mode,
+            line_difficulty: options.generate.line_difficulty,
+            format: options.format,
+            unique_goals: options.unique_goals,
+            boards,
+        };
+        std::fs::write(
+            dir.join("index.json"),
+            serde_json::to_string_pretty(&index)?,
+        )?;
+
+        self.record_usage(
+            index
+                .boards
+                .into_iter()
+                .map(|board| (format!("{}/{}", folder, board.file), board.goals))
+                .collect(),
+        )?;
+
+        Ok(folder)
+    }
+}
+
+/// Reports how far a long job got with `BulkProgress`, at most every [`PROGRESS_INTERVAL`] and
+/// always when it's done.
+pub(crate) struct Progress {
+    send: FrontendHandle,
+    total: usize,
+    last_sent: Option<Instant>,
+}
+
+impl Progress {
+    pub(crate) fn new(send: FrontendHandle, total: usize) -> Self {
+        Self {
+            send,
+            total,
+            last_sent: None,
+        }
+    }
+
+    pub(crate) fn update(&mut self, done: usize) {
+        if done < self.total
+            && self
+                .last_sent
+                .is_some_and(|sent| sent.elapsed() < PROGRESS_INTERVAL)
+        {
+            return;
+        }
+
+        self.last_sent = Some(Instant::now());
+        self.send.send(MessageToFrontend::BulkProgress {
+            done,
+            total: self.total,
+        });
+    }
+}
+
+/// Writes a board's goal texts, row-major, in the given export format.
+pub(crate) fn write_board(
+    path: &Path,
+    format: ExportFormat,
+    game_name: &str,
+    grid_size: GridSize,
+    goals: &[String],
+) -> Result<()> {
+    let data = match format {
+        ExportFormat::BingoSync => {
+            if grid_size > GridSize::Size5 {
+                bail!("Bingosync boards can't be bigger than 5x5");
+            }
+            serde_json::to_string_pretty(&BingoSyncCard::board(grid_size, goals))?
+        }
+        ExportFormat::LockoutLive => {
+            if let Some(goal) = goals.iter().find(|g| g.len() > 60) {
+                bail!(
+                    "Lockout Live can't have a task text longer than 60 characters: '{}'",
+                    goal
+                );
+            }
+            serde_json::to_string_pretty(&LockoutLiveBoard::from_cells(game_name, goals))?
+        }
+    };
+    std::fs::write(path, data)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use bridge::goal::Goal;
+
+    use super::*;
+
+    fn options(count: usize) -> BulkOptions {
+        BulkOptions {
+            count,
+            first_seed: Some(0),
+            generate: GenerateOptions {
+                grid_size: GridSize::Size3,
+                ..Default::default()
+            },
+            format: ExportFormat::BingoSync,
+            unique_goals: true,
+        }
+    }
+
+    #[test]
+    fn unique_goals_never_repeat_across_boards() {
+        let (state, _recv) = BackendState::for_test("bulk");
+        let pool = GoalPool {
+            goals: (0..30)
+                .map(|i| Goal {
+                    name: format!("Goal {i}"),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let folder = state.write_bulk(pool.clone(), None, options(3)).unwrap();
+        let dir = state.export_dir.join(&folder);
+        let index: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.join("index.json")).unwrap())
+                .unwrap();
+
+        let boards = index["boards"].as_array().unwrap();
+        assert_eq!(boards.len(), 3);
+
+        let mut seen = HashSet::new();
+        for board in boards {
+            assert!(dir.join(board["file"].as_str().unwrap()).exists());
+            for goal in board["goals"].as_array().unwrap() {
+                let goal = goal.as_str().unwrap();
+                assert!(seen.insert(goal.to_owned()), "'{goal}' repeated");
+            }
+        }
+        assert_eq!(seen.len(), 27);
+
+        let history = state.read_usage_history().unwrap();
+        assert_eq!(history.boards.len(), 3);
+        assert_eq!(history.boards[0].file, format!("{folder}/board_001.json"));
+
+        let error = state.write_bulk(pool, None, options(4)).unwrap_err();
+        assert_eq!(error.to_string(), "Board 4 with seed 3");
+    }
+}
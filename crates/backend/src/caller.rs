@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bridge::caller::{CallerGame, CardSet};
+use rand::{SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{backend::BackendState, pdf};
+
+impl BackendState {
+    /// Reads a card set and resumes its saved game, or starts a new one if there's none.
+    pub async fn open_card_set(&self, path: &Path) -> Result<(CardSet, CallerGame)> {
+        let card_set = read_card_set(path)?;
+        let game_path = game_path(path);
+
+        if game_path.exists() {
+            let game: CallerGame = serde_json::from_str(&std::fs::read_to_string(&game_path)?)
+                .with_context(|| format!("Saved game '{}'", game_path.display()))?;
+            return Ok((card_set, game));
+        }
+
+        let game = new_game(path, &card_set, None);
+        save_game(path, &game)?;
+
+        Ok((card_set, game))
+    }
+
+    /// Throws away the saved game of a card set and starts over with a new call order.
+    pub async fn restart_caller_game(
+        &self,
+        path: &Path,
+        seed: Option<u64>,
+    ) -> Result<(CardSet, CallerGame)> {
+        let card_set = read_card_set(path)?;
+        let game = new_game(path, &card_set, seed);
+        save_game(path, &game)?;
+
+        Ok((card_set, game))
+    }
+
+    /// Writes the saved game's call order next to the card set, returns the file name.
+    pub async fn export_caller_sheet(&self, path: &Path) -> Result<String> {
+        let card_set = read_card_set(path)?;
+        let game: CallerGame = serde_json::from_str(&std::fs::read_to_string(game_path(path))?)?;
+
+        let filename = format!(
+            "{}_caller_sheet.pdf",
+            path.file_stem().unwrap_or_default().to_string_lossy()
+        );
+        pdf::write_caller_sheet(&path.with_file_name(&filename), &card_set, &game)?;
+
+        Ok(filename)
+    }
+}
+
+pub fn read_card_set(path: &Path) -> Result<CardSet> {
+    let card_set: CardSet = serde_json::from_str(&std::fs::read_to_string(path)?)
+        .with_context(|| format!("'{}' isn't a card set", path.display()))?;
+
+    if card_set.cards.is_empty() {
+        bail!("Card set '{}' has no cards", path.display());
+    }
+
+    Ok(card_set)
+}
+
+pub fn save_game(path: &Path, game: &CallerGame) -> Result<()> {
+    std::fs::write(game_path(path), serde_json::to_string_pretty(game)?)?;
+
+    Ok(())
+}
+
+/// `X_cards.json` keeps its game in `X_cards.game.json`.
+fn game_path(path: &Path) -> PathBuf {
+    path.with_extension("game.json")
+}
+
+fn new_game(path: &Path, card_set: &CardSet, seed: Option<u64>) -> CallerGame {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut order = card_set.goals();
+    order.shuffle(&mut rng);
+
+    CallerGame {
+        card_set_file: path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned(),
+        seed,
+        order,
+        called: 0,
+    }
+}
@@ -1,31 +1,384 @@
-use anyhow::Result;
-use bridge::card::{BingoSyncCard, LockoutLiveBoard};
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Result, bail};
+use bridge::{
+    card::{BingoSyncCard, LockoutLiveBoard, LockoutLiveCard},
+    goal::{Goal, GoalFilter, GoalParam, GoalPool},
+    metadata::ExportMetadata,
+};
 use chrono::Local;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{backend::BackendState, generator};
 
-use crate::backend::BackendState;
+const BINGO_SYNC_BOARD_SIZE: usize = 25;
+const LOCKOUT_LIVE_BOARD_SIZE: usize = 25;
 
 impl BackendState {
-    pub async fn export_bingo_sync(&self, data: Vec<BingoSyncCard>) -> Result<String> {
+    pub async fn export_bingo_sync(
+        &self,
+        data: Vec<BingoSyncCard>,
+        metadata: Option<ExportMetadata>,
+    ) -> Result<String> {
         let date = Local::now();
         let filename = format!("{}_bingo_sync.json", date.format("%Y-%m-%d_%H-%M-%S"));
         std::fs::write(
             self.export_dir.join(filename.clone()),
             serde_json::to_string_pretty(&data)?,
-        )
-        .ok();
+        )?;
+
+        if let Some(metadata) = metadata {
+            self.write_sidecar(&filename, metadata)?;
+        }
+        self.record_usage(vec![(
+            filename.clone(),
+            data.into_iter().map(|c| c.name).collect(),
+        )])?;
 
         Ok(filename)
     }
 
-    pub async fn export_lockout_live(&self, data: LockoutLiveBoard) -> Result<String> {
+    pub async fn export_lockout_live(
+        &self,
+        data: LockoutLiveBoard,
+        metadata: Option<ExportMetadata>,
+    ) -> Result<String> {
         let date = Local::now();
         let filename = format!("{}_lockout_live.json", date.format("%Y-%m-%d_%H-%M-%S"));
         std::fs::write(
             self.export_dir.join(filename.clone()),
             serde_json::to_string_pretty(&data)?,
-        )
-        .ok();
+        )?;
+
+        if let Some(metadata) = metadata {
+            self.write_sidecar(&filename, metadata)?;
+        }
+        self.record_usage(vec![(
+            filename.clone(),
+            data.objectives.into_iter().map(|c| c.goal).collect(),
+        )])?;
+
+        Ok(filename)
+    }
+
+    pub async fn export_bingo_sync_pool(
+        &self,
+        pool: GoalPool,
+        filter: GoalFilter,
+        seed: Option<u64>,
+    ) -> Result<(String, u64)> {
+        let seed = seed.unwrap_or_else(rand::random);
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let pool = generator::expand_templates(&pool, &mut rng);
+        let data: Vec<BingoSyncCard> = self
+            .unique_goals(&pool, &filter)
+            .into_iter()
+            .map(|g| BingoSyncCard {
+                name: g.name.trim().to_owned(),
+            })
+            .collect();
+
+        if data.len() < BINGO_SYNC_BOARD_SIZE {
+            bail!(
+                "Bingosync needs at least {} unique goals, filtered pool has {}",
+                BINGO_SYNC_BOARD_SIZE,
+                data.len()
+            );
+        }
+
+        let date = Local::now();
+        let filename = format!("{}_bingo_sync_pool.json", date.format("%Y-%m-%d_%H-%M-%S"));
+        std::fs::write(
+            self.export_dir.join(filename.clone()),
+            serde_json::to_string_pretty(&data)?,
+        )?;
+
+        Ok((filename, seed))
+    }
+
+    pub async fn export_lockout_live_pool(
+        &self,
+        pool: GoalPool,
+        filter: GoalFilter,
+    ) -> Result<String> {
+        let goals = self.unique_goals(&pool, &filter);
+
+        if goals.len() < LOCKOUT_LIVE_BOARD_SIZE {
+            bail!(
+                "Lockout Live needs at least {} unique goals, filtered pool has {}",
+                LOCKOUT_LIVE_BOARD_SIZE,
+                goals.len()
+            );
+        }
+
+        let goals = goals
+            .into_iter()
+            .map(lockout_live_goals)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if let Some((goal, _)) = goals.iter().find(|(g, _)| g.name.trim().len() > 60) {
+            bail!(
+                "Lockout Live can't have a task text longer than 60 characters: '{}'",
+                goal.name.trim()
+            );
+        }
+
+        let objectives = lockout_live_objectives(goals);
+
+        let game_name = if pool.game_name.is_empty() {
+            "None".to_owned()
+        } else {
+            pool.game_name.clone()
+        };
+
+        let data = LockoutLiveBoard {
+            schema_version: 3,
+            schema_mode: "relaxed".to_owned(),
+            game_name,
+            tag_names: Default::default(),
+            objectives,
+            limits: HashMap::from([
+                ("board".to_string(), pool.board_limits.clone()),
+                ("line".to_string(), pool.line_limits.clone()),
+            ]),
+        };
+
+        let date = Local::now();
+        let filename = format!(
+            "{}_lockout_live_pool.json",
+            date.format("%Y-%m-%d_%H-%M-%S")
+        );
+        std::fs::write(
+            self.export_dir.join(filename.clone()),
+            serde_json::to_string_pretty(&data)?,
+        )?;
 
         Ok(filename)
     }
+
+    fn unique_goals<'a>(&self, pool: &'a GoalPool, filter: &GoalFilter) -> Vec<&'a Goal> {
+        let mut seen = HashSet::new();
+        let mut duplicates = 0;
+
+        let goals: Vec<&Goal> = pool
+            .filtered(filter)
+            .into_iter()
+            .filter(|g| !g.name.trim().is_empty())
+            .filter(|g| {
+                let unique = seen.insert(g.name.trim().to_owned());
+                if !unique {
+                    duplicates += 1;
+                }
+                unique
+            })
+            .collect();
+
+        if duplicates > 0 {
+            self.send
+                .send_warning(format!("Skipped {} duplicate goals", duplicates));
+        }
+
+        goals
+    }
+}
+
+/// Lockout Live fills a goal's `X` from its range, so a template becomes one goal per choice
+/// combination with its numeric range, if it has one, left as `X`.
+fn lockout_live_goals(goal: &Goal) -> Result<Vec<(Goal, Vec<String>)>> {
+    let mut ranges = goal.params.iter().filter_map(|(key, param)| match param {
+        GoalParam::Range { min, max, .. } => Some((key, min, max)),
+        GoalParam::Choice(_) => None,
+    });
+    let range = ranges.next();
+    if ranges.next().is_some() {
+        bail!(
+            "Lockout Live goals can only have one number range: '{}'",
+            goal.name.trim()
+        );
+    }
+
+    let mut template = goal.clone();
+    let mut card_range = Vec::new();
+    if let Some((key, min, max)) = range {
+        template.name = template.name.replace(&format!("{{{key}}}"), "X");
+        template.params.remove(key);
+        card_range = vec![min.to_string(), max.to_string()];
+    }
+
+    if !template.is_template() {
+        return Ok(vec![(template, card_range)]);
+    }
+
+    let mut goals = vec![template.name.clone()];
+    for (key, param) in &template.params {
+        goals = goals
+            .iter()
+            .flat_map(|name| {
+                param
+                    .values()
+                    .into_iter()
+                    .map(move |value| name.replace(&format!("{{{key}}}"), &value))
+            })
+            .collect();
+    }
+
+    Ok(goals
+        .into_iter()
+        .map(|name| {
+            let goal = Goal {
+                name,
+                params: Default::default(),
+                ..template.clone()
+            };
+            (goal, card_range.clone())
+        })
+        .collect())
+}
+
+/// Objectives with the goal's categories as both board and line categories and its difficulty
+/// third as progression, goals without a difficulty can show up anywhere.
+fn lockout_live_objectives(goals: Vec<(Goal, Vec<String>)>) -> Vec<LockoutLiveCard> {
+    let min = goals
+        .iter()
+        .map(|(g, _)| g.difficulty)
+        .filter(|&d| d > 0)
+        .min();
+    let max = goals.iter().map(|(g, _)| g.difficulty).max();
+
+    goals
+        .into_iter()
+        .map(|(g, range)| {
+            let progression = match (min, max) {
+                (Some(min), Some(max)) if g.difficulty > 0 => {
+                    vec![progression_tier(g.difficulty, min, max).to_owned()]
+                }
+                _ => LockoutLiveCard::default().progression,
+            };
+
+            let mut card = LockoutLiveCard::from_goal(
+                g.name.trim().to_owned(),
+                g.categories.clone(),
+                progression,
+            );
+            card.range = range;
+            card
+        })
+        .collect()
+}
+
+/// Splits the pool difficulty range into lockout.live early/mid/late thirds.
+fn progression_tier(difficulty: usize, min: usize, max: usize) -> &'static str {
+    if max <= min {
+        return "e";
+    }
+
+    match (difficulty.saturating_sub(min) * 3) / (max - min + 1) {
+        0 => "e",
+        1 => "m",
+        _ => "l",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn goal(name: &str, difficulty: usize, categories: &[&str]) -> Goal {
+        Goal {
+            name: name.to_owned(),
+            difficulty,
+            categories: categories.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn progression_splits_difficulty_in_thirds() {
+        let tiers: Vec<&str> = (1..=9).map(|d| progression_tier(d, 1, 9)).collect();
+        assert_eq!(tiers, ["e", "e", "e", "m", "m", "m", "l", "l", "l"]);
+
+        assert_eq!(progression_tier(2, 2, 4), "e");
+        assert_eq!(progression_tier(3, 2, 4), "m");
+        assert_eq!(progression_tier(4, 2, 4), "l");
+        assert_eq!(progression_tier(5, 5, 5), "e");
+    }
+
+    #[test]
+    fn objectives_keep_categories_and_progression() {
+        let objectives = lockout_live_objectives(vec![
+            (goal(" Boss ", 1, &["combat"]), vec![]),
+            (goal("Gems", 3, &["collect", "early"]), vec![]),
+            (goal("Anything", 0, &[]), vec![]),
+        ]);
+
+        let boss = serde_json::to_value(&objectives[0]).unwrap();
+        assert_eq!(boss["goal"], "Boss");
+        assert_eq!(boss["board_categories"], serde_json::json!(["combat"]));
+        assert_eq!(boss["line_categories"], serde_json::json!(["combat"]));
+        assert_eq!(boss["progression"], serde_json::json!(["e"]));
+        assert_eq!(boss["forced_positions"], serde_json::json!([]));
+
+        let gems = serde_json::to_value(&objectives[1]).unwrap();
+        assert_eq!(
+            gems["board_categories"],
+            serde_json::json!(["collect", "early"])
+        );
+        assert_eq!(gems["progression"], serde_json::json!(["l"]));
+
+        assert_eq!(
+            objectives[2].progression,
+            LockoutLiveCard::default().progression
+        );
+    }
+
+    #[test]
+    fn templates_leave_range_as_x() {
+        let mut template = goal("Collect {n} {gem} gems", 2, &["collect"]);
+        template.params = BTreeMap::from([
+            (
+                "n".to_owned(),
+                GoalParam::Range {
+                    min: 3,
+                    max: 7,
+                    step: 1,
+                    difficulty_step: 1.0,
+                },
+            ),
+            (
+                "gem".to_owned(),
+                GoalParam::Choice(vec!["red".to_owned(), "blue".to_owned()]),
+            ),
+        ]);
+
+        let goals = lockout_live_goals(&template).unwrap();
+        let names: Vec<&str> = goals.iter().map(|(g, _)| g.name.as_str()).collect();
+        assert_eq!(names, ["Collect X red gems", "Collect X blue gems"]);
+        assert!(goals.iter().all(|(g, range)| {
+            !g.is_template() && g.categories == ["collect"] && range == &["3", "7"]
+        }));
+
+        let objectives = lockout_live_objectives(goals);
+        assert_eq!(objectives[0].range, ["3", "7"]);
+    }
+
+    #[test]
+    fn templates_allow_one_range() {
+        let mut template = goal("{a} and {b}", 1, &[]);
+        let range = GoalParam::Range {
+            min: 1,
+            max: 2,
+            step: 1,
+            difficulty_step: 0.0,
+        };
+        template.params =
+            BTreeMap::from([("a".to_owned(), range.clone()), ("b".to_owned(), range)]);
+
+        assert!(lockout_live_goals(&template).is_err());
+    }
 }
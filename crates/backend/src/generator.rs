@@ -0,0 +1,651 @@
+use anyhow::{Result, bail};
+use bridge::{
+    board::{BoardConstraints, GenerateMode, GenerateOptions, GeneratedBoard, GridSize},
+    goal::{Goal, GoalPool},
+    history::{AvoidMode, AvoidRecent},
+    rules,
+};
+use rand::{Rng, SeedableRng, seq::SliceRandom};
+use rand_chacha::ChaCha8Rng;
+
+use crate::solver;
+
+const GENERATE_ATTEMPTS: usize = 20;
+const REFINE_ROUNDS_PER_CELL: usize = 400;
+
+pub fn generate(pool: &GoalPool, options: &GenerateOptions) -> Result<GeneratedBoard> {
+    let mut board = generate_goals(pool, options)?;
+    board.mode = options.mode;
+    board.line_difficulty = options.line_difficulty;
+    board.pinned = options.pinned.clone();
+    board.avoided_recent = options
+        .avoid_recent
+        .as_ref()
+        .is_some_and(|avoid| !avoid.uses.is_empty());
+
+    Ok(board)
+}
+
+fn generate_goals(pool: &GoalPool, options: &GenerateOptions) -> Result<GeneratedBoard> {
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut pool = expand_templates(pool, &mut rng);
+    let grid_size = options.grid_size;
+    let cells = grid_size.cell_count();
+
+    if let Some(avoid) = &options.avoid_recent
+        && !avoid.uses.is_empty()
+    {
+        avoid_recent(
+            &mut pool,
+            avoid,
+            cells.saturating_sub(options.pinned.len()),
+            &mut rng,
+        );
+    }
+    let pool = &pool;
+
+    let pinned: Vec<(usize, &Goal)> = options
+        .pinned
+        .iter()
+        .filter(|(cell, _)| **cell < cells)
+        .map(|(cell, goal)| (*cell, goal))
+        .collect();
+    let free_cells = cells - pinned.len();
+
+    let mut candidates: Vec<&Goal> = pool
+        .goals
+        .iter()
+        .filter(|g| !g.name.trim().is_empty())
+        .filter(|g| pinned.iter().all(|(_, p)| p.name.trim() != g.name.trim()))
+        .collect();
+
+    if candidates.len() < free_cells {
+        bail!(
+            "Goal pool has {} goals left, a {}x{} board with {} pinned cells needs {}",
+            candidates.len(),
+            grid_size.side(),
+            grid_size.side(),
+            pinned.len(),
+            free_cells
+        );
+    }
+
+    if options.mode == GenerateMode::Constrained {
+        let mut constraints = BoardConstraints::from_pool(pool);
+        constraints.line_difficulty = options.line_difficulty;
+        constraints.pinned = options.pinned.clone();
+
+        let goals = solver::solve(pool, &constraints, grid_size, &mut rng)?;
+
+        return Ok(GeneratedBoard::new(grid_size, seed, goals));
+    }
+
+    let mut most_picked = 0;
+    for _ in 0..GENERATE_ATTEMPTS {
+        shuffle_weighted(&mut candidates, &mut rng);
+
+        let picked = pick_goals(&candidates, &pinned, free_cells);
+        most_picked = most_picked.max(picked.len());
+        if picked.len() < free_cells {
+            continue;
+        }
+
+        let (goals, line_conflicts) = place(
+            pool,
+            grid_size,
+            &pinned,
+            picked,
+            options.mode == GenerateMode::Balanced,
+            &mut rng,
+        );
+        if line_conflicts > 0 {
+            continue;
+        }
+
+        return Ok(GeneratedBoard::new(
+            grid_size,
+            seed,
+            goals.into_iter().cloned().collect(),
+        ));
+    }
+
+    if most_picked < free_cells {
+        bail!(
+            "Exclusion groups leave at most {} goals, a {}x{} board with {} pinned cells needs {}",
+            most_picked,
+            grid_size.side(),
+            grid_size.side(),
+            pinned.len(),
+            free_cells
+        );
+    }
+
+    bail!(
+        "Couldn't place goals on a {}x{} board without breaking line rules",
+        grid_size.side(),
+        grid_size.side()
+    );
+}
+
+/// Swaps the goals in `cells` for unused pool goals of the closest difficulty, preferring ones
+/// that share a category with the goal they replace, so line balance and rules still hold. A
+/// template that's already on the board isn't expanded a second time.
+pub fn reroll(
+    pool: &GoalPool,
+    grid_size: GridSize,
+    seed: Option<u64>,
+    board: &[Goal],
+    cells: &[usize],
+) -> Result<GeneratedBoard> {
+    let seed = seed.unwrap_or_else(rand::random);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let expanded = expand_templates(pool, &mut rng);
+
+    if board.len() != grid_size.cell_count() {
+        bail!(
+            "Board has {} cells, a {}x{} board needs {}",
+            board.len(),
+            grid_size.side(),
+            grid_size.side(),
+            grid_size.cell_count()
+        );
+    }
+
+    if let Some(&cell) = cells.iter().find(|&&cell| cell >= board.len()) {
+        bail!(
+            "Cell {} is outside of a {}x{} board",
+            cell + 1,
+            grid_size.side(),
+            grid_size.side()
+        );
+    }
+
+    // Saved boards don't keep the template a goal was expanded from, look it up again so no
+    // other expansion of a template that's still on the board comes back.
+    let original: Vec<Goal> = board
+        .iter()
+        .map(|goal| match goal.template {
+            Some(_) => goal.clone(),
+            None => Goal {
+                template: pool.resolve(&goal.name).and_then(|g| g.template),
+                ..goal.clone()
+            },
+        })
+        .collect();
+    let pool = &expanded;
+
+    let lines = grid_size.lines();
+    let mut goals = original.clone();
+
+    for &cell in cells {
+        let old = &original[cell];
+        let cell_lines: Vec<&Vec<usize>> = lines.iter().filter(|l| l.contains(&cell)).collect();
+
+        let mut candidates: Vec<&Goal> = pool
+            .goals
+            .iter()
+            .filter(|g| !g.name.trim().is_empty())
+            .filter(|g| {
+                original
+                    .iter()
+                    .chain(goals.iter())
+                    .all(|b| b.name.trim() != g.name.trim())
+            })
+            .filter(|g| {
+                goals
+                    .iter()
+                    .enumerate()
+                    .all(|(idx, b)| idx == cell || !rules::excludes(b, g))
+            })
+            .filter(|g| {
+                cell_lines.iter().all(|line| {
+                    line.iter()
+                        .all(|&idx| idx == cell || !rules::conflicts(pool, &goals[idx], g))
+                })
+            })
+            .collect();
+
+        candidates.shuffle(&mut rng);
+
+        let score = |g: &Goal| {
+            let shares_category = old.categories.is_empty()
+                || g.categories.iter().any(|c| old.categories.contains(c));
+            (g.difficulty.abs_diff(old.difficulty), !shares_category)
+        };
+
+        let Some(best) = candidates.iter().map(|g| score(g)).min() else {
+            bail!(
+                "No goal left that can replace '{}' without breaking exclusion or line rules",
+                old.name.trim()
+            );
+        };
+        let replacement = candidates.into_iter().find(|g| score(g) == best).unwrap();
+
+        goals[cell] = replacement.clone();
+    }
+
+    let mut board = GeneratedBoard::new(grid_size, seed, goals);
+    board.rerolled = true;
+
+    Ok(board)
+}
+
+/// Copy of the pool with every template goal replaced by one random expansion of it.
+pub fn expand_templates(pool: &GoalPool, rng: &mut impl Rng) -> GoalPool {
+    let goals = pool
+        .goals
+        .iter()
+        .map(|goal| {
+            if !goal.is_template() {
+                return goal.clone();
+            }
+
+            let picks = goal
+                .params
+                .iter()
+                .map(|(key, param)| {
+                    let len = param.values().len().max(1);
+                    (key.clone(), rng.random_range(0..len))
+                })
+                .collect();
+            goal.instantiate(&picks)
+        })
+        .collect();
+
+    GoalPool {
+        goals,
+        ..pool.clone()
+    }
+}
+
+/// Drops recently used goals from the pool. Down-weighted goals stay with a chance of
+/// `1 / (uses + 1)`, and the least used of the dropped ones come back if fewer than `needed` are
+/// left.
+fn avoid_recent(pool: &mut GoalPool, avoid: &AvoidRecent, needed: usize, rng: &mut impl Rng) {
+    let uses = |g: &Goal| avoid.uses.get(g.pool_name()).copied().unwrap_or(0);
+
+    let (mut kept, mut dropped): (Vec<Goal>, Vec<Goal>) =
+        pool.goals.drain(..).partition(|g| match avoid.mode {
+            AvoidMode::Exclude => uses(g) == 0,
+            AvoidMode::DownWeight => rng.random_bool(1.0 / (uses(g) + 1) as f64),
+        });
+
+    if avoid.mode == AvoidMode::DownWeight && kept.len() < needed {
+        dropped.sort_by_key(|g| uses(g));
+        let missing = (needed - kept.len()).min(dropped.len());
+        kept.extend(dropped.drain(..missing));
+    }
+
+    pool.goals = kept;
+}
+
+/// Shuffles so goals with a higher weight tend to come first. Plain shuffle when no goal has a
+/// weight, so seeds from pools without weights keep their boards.
+pub(crate) fn shuffle_weighted<'a>(goals: &mut [&'a Goal], rng: &mut impl Rng) {
+    if goals.iter().all(|g| g.weight.is_none()) {
+        goals.shuffle(rng);
+        return;
+    }
+
+    // Sorting by u^(1 / weight) gives a weighted random order.
+    let mut keyed: Vec<(f64, &'a Goal)> = goals
+        .iter()
+        .map(|&g| (rng.random::<f64>().powf(1.0 / g.weight()), g))
+        .collect();
+    keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    for (slot, (_, goal)) in goals.iter_mut().zip(keyed) {
+        *slot = goal;
+    }
+}
+
+/// Takes goals in order, skipping any that share an exclusion group with one already taken or
+/// pinned.
+fn pick_goals<'a>(
+    candidates: &[&'a Goal],
+    pinned: &[(usize, &Goal)],
+    count: usize,
+) -> Vec<&'a Goal> {
+    let mut goals: Vec<&Goal> = Vec::with_capacity(count);
+
+    for &goal in candidates {
+        if goals.len() == count {
+            break;
+        }
+        if goals.iter().all(|g| !rules::excludes(g, goal))
+            && pinned.iter().all(|(_, g)| !rules::excludes(g, goal))
+        {
+            goals.push(goal);
+        }
+    }
+
+    goals
+}
+
+/// Lays the goals out on the board around the pinned ones, returning them row-major along with
+/// the number of line rule conflicts that couldn't be resolved.
+fn place<'a>(
+    pool: &GoalPool,
+    grid_size: GridSize,
+    pinned: &[(usize, &'a Goal)],
+    picked: Vec<&'a Goal>,
+    balanced: bool,
+    rng: &mut impl Rng,
+) -> (Vec<&'a Goal>, usize) {
+    let cells = grid_size.cell_count();
+    let goals: Vec<&Goal> = pinned.iter().map(|(_, g)| *g).chain(picked).collect();
+    let mut placement: Vec<usize> = (0..cells).collect();
+
+    // Goals ranked by difficulty on a magic square give every line a similar mix.
+    if balanced {
+        let mut ranked: Vec<usize> = (0..cells).collect();
+        ranked.sort_by_key(|&idx| goals[idx].difficulty);
+
+        let template: Vec<usize> = match magic_square(grid_size.side()) {
+            Some(square) => random_symmetry(grid_size.side(), square, rng)
+                .into_iter()
+                .map(|v| v - 1)
+                .collect(),
+            None => {
+                let mut template: Vec<usize> = (0..cells).collect();
+                template.shuffle(rng);
+                template
+            }
+        };
+        placement = template.into_iter().map(|rank| ranked[rank]).collect();
+    }
+
+    // Pinned goals come first in `goals`, move each onto its cell.
+    for (idx, &(cell, _)) in pinned.iter().enumerate() {
+        if let Some(current) = placement.iter().position(|&g| g == idx) {
+            placement.swap(current, cell);
+        }
+    }
+
+    let free: Vec<usize> = (0..cells)
+        .filter(|cell| pinned.iter().all(|(c, _)| c != cell))
+        .collect();
+
+    let board = BoardCost {
+        lines: grid_size.lines(),
+        difficulties: goals.iter().map(|g| g.difficulty).collect(),
+        conflicts: goals
+            .iter()
+            .flat_map(|a| goals.iter().map(|b| rules::conflicts(pool, a, b)))
+            .collect(),
+        balanced,
+    };
+    let cost = refine(&board, &mut placement, &free, rng);
+
+    (
+        placement.into_iter().map(|idx| goals[idx]).collect(),
+        cost.0,
+    )
+}
+
+struct BoardCost {
+    lines: Vec<Vec<usize>>,
+    /// By goal index.
+    difficulties: Vec<usize>,
+    /// Goal index pairs that can't share a line, `a * cells + b`.
+    conflicts: Vec<bool>,
+    balanced: bool,
+}
+
+impl BoardCost {
+    /// Line rule conflicts first, then line spread and squared deviation from the mean line total
+    /// when balancing.
+    fn of(&self, placement: &[usize]) -> (usize, usize, f64) {
+        let cells = placement.len();
+        let conflicts = self
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .enumerate()
+                    .flat_map(|(i, &a)| line[i + 1..].iter().map(move |&b| (a, b)))
+                    .filter(|&(a, b)| self.conflicts[placement[a] * cells + placement[b]])
+                    .count()
+            })
+            .sum();
+
+        if !self.balanced {
+            return (conflicts, 0, 0.0);
+        }
+
+        let totals: Vec<usize> = self
+            .lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|&idx| self.difficulties[placement[idx]])
+                    .sum()
+            })
+            .collect();
+
+        let min = totals.iter().copied().min().unwrap_or_default();
+        let max = totals.iter().copied().max().unwrap_or_default();
+        let mean = totals.iter().sum::<usize>() as f64 / totals.len() as f64;
+        let deviation = totals.iter().map(|&t| (t as f64 - mean).powi(2)).sum();
+
+        (conflicts, max - min, deviation)
+    }
+}
+
+/// Hill climbs on swaps between the `free` cells, keeping any swap that doesn't make the board
+/// worse.
+fn refine(
+    board: &BoardCost,
+    placement: &mut [usize],
+    free: &[usize],
+    rng: &mut impl Rng,
+) -> (usize, usize, f64) {
+    let mut best = board.of(placement);
+    if free.len() < 2 {
+        return best;
+    }
+
+    for _ in 0..placement.len() * REFINE_ROUNDS_PER_CELL {
+        if best.0 == 0 && best.1 == 0 {
+            break;
+        }
+
+        let a = free[rng.random_range(0..free.len())];
+        let b = free[rng.random_range(0..free.len())];
+        if a == b {
+            continue;
+        }
+
+        placement.swap(a, b);
+        let cost = board.of(placement);
+        if cost <= best {
+            best = cost;
+        } else {
+            placement.swap(a, b);
+        }
+    }
+
+    best
+}
+
+/// Row-major magic square with values `1..=n*n`, for odd and doubly even sides.
+fn magic_square(n: usize) -> Option<Vec<usize>> {
+    if n % 2 == 1 {
+        Some(
+            (0..n * n)
+                .map(|idx| {
+                    let (y, x) = (idx / n, idx % n);
+                    n * ((y + x + 1 + n / 2) % n) + (y + 2 * x + 1) % n + 1
+                })
+                .collect(),
+        )
+    } else if n.is_multiple_of(4) {
+        Some(
+            (0..n * n)
+                .map(|idx| {
+                    let (y, x) = (idx / n % 4, idx % n % 4);
+                    if y == x || y + x == 3 {
+                        n * n - idx
+                    } else {
+                        idx + 1
+                    }
+                })
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
+
+/// One of the 8 rotations/reflections of the square, all of them keep it magic.
+fn random_symmetry(n: usize, square: Vec<usize>, rng: &mut impl Rng) -> Vec<usize> {
+    let transpose = rng.random_bool(0.5);
+    let flip_x = rng.random_bool(0.5);
+    let flip_y = rng.random_bool(0.5);
+
+    (0..n * n)
+        .map(|idx| {
+            let (mut y, mut x) = (idx / n, idx % n);
+            if transpose {
+                (y, x) = (x, y);
+            }
+            if flip_x {
+                x = n - 1 - x;
+            }
+            if flip_y {
+                y = n - 1 - y;
+            }
+            square[y * n + x]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bridge::goal::GoalParam;
+
+    use super::*;
+
+    fn line_totals(grid_size: GridSize, values: &[usize]) -> Vec<usize> {
+        grid_size
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|&idx| values[idx]).sum())
+            .collect()
+    }
+
+    #[test]
+    fn magic_square_lines_sum_equal() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        for grid_size in (3..=9).filter_map(GridSize::from_repr) {
+            let n = grid_size.side();
+            let Some(square) = magic_square(n) else {
+                assert!(n % 2 == 0 && !n.is_multiple_of(4), "no square for {n}");
+                continue;
+            };
+
+            let mut sorted = square.clone();
+            sorted.sort();
+            assert_eq!(sorted, (1..=n * n).collect::<Vec<_>>(), "values of {n}");
+
+            let magic = n * (n * n + 1) / 2;
+            for values in [square.clone(), random_symmetry(n, square, &mut rng)] {
+                assert!(
+                    line_totals(grid_size, &values).iter().all(|&t| t == magic),
+                    "lines of {n}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reroll_skips_templates_on_board() {
+        let plain = |i: usize, difficulty| Goal {
+            name: format!("Goal {i}"),
+            difficulty,
+            ..Default::default()
+        };
+        let template = Goal {
+            name: "Collect {n} coins".to_owned(),
+            difficulty: 5,
+            params: BTreeMap::from([(
+                "n".to_owned(),
+                GoalParam::Range {
+                    min: 1,
+                    max: 3,
+                    step: 1,
+                    difficulty_step: 0.0,
+                },
+            )]),
+            ..Default::default()
+        };
+        let mut goals: Vec<Goal> = (0..9).map(|i| plain(i, 1)).collect();
+        goals[0].difficulty = 5;
+        goals.push(template);
+        let pool = GoalPool {
+            goals,
+            ..Default::default()
+        };
+
+        // Like a board read back from a file, the expanded goal has no template.
+        let mut board: Vec<Goal> = pool.goals[..8].to_vec();
+        board.push(Goal {
+            name: "Collect 2 coins".to_owned(),
+            difficulty: 5,
+            ..Default::default()
+        });
+
+        for seed in 0..10 {
+            let rerolled = reroll(&pool, GridSize::Size3, Some(seed), &board, &[0]).unwrap();
+            assert_eq!(rerolled.goals[0].name, "Goal 8");
+        }
+    }
+
+    #[test]
+    fn reroll_rejects_cells_off_the_board() {
+        let pool = GoalPool {
+            goals: (0..12)
+                .map(|i| Goal {
+                    name: format!("Goal {i}"),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let board = &pool.goals[..9];
+
+        let error = reroll(&pool, GridSize::Size3, Some(0), board, &[9]).unwrap_err();
+        assert_eq!(error.to_string(), "Cell 10 is outside of a 3x3 board");
+    }
+
+    #[test]
+    fn refine_balances_singly_even() {
+        let grid_size = GridSize::Size6;
+        let cells = grid_size.cell_count();
+        let board = BoardCost {
+            lines: grid_size.lines(),
+            difficulties: (1..=cells).collect(),
+            conflicts: vec![false; cells * cells],
+            balanced: true,
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let mut placement: Vec<usize> = (0..cells).collect();
+        let before = board.of(&placement);
+        let free: Vec<usize> = (0..cells).collect();
+        let after = refine(&board, &mut placement, &free, &mut rng);
+
+        let totals = line_totals(
+            grid_size,
+            &placement.iter().map(|&g| g + 1).collect::<Vec<_>>(),
+        );
+        let spread = totals.iter().max().unwrap() - totals.iter().min().unwrap();
+        assert_eq!(after, board.of(&placement));
+        assert_eq!(after.1, spread);
+        assert!(spread < before.1 / 10, "spread {spread} from {}", before.1);
+    }
+}
@@ -0,0 +1,60 @@
+use anyhow::Result;
+use bridge::{
+    history::{HistoryEntry, UsageHistory},
+    message::MessageToFrontend,
+};
+
+use crate::backend::BackendState;
+
+const HISTORY_FILE: &str = "usage_history.json";
+
+impl BackendState {
+    pub fn read_usage_history(&self) -> Result<UsageHistory> {
+        let path = self.export_dir.join(HISTORY_FILE);
+        if !path.exists() {
+            return Ok(UsageHistory::default());
+        }
+
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    pub fn send_usage_history(&self) {
+        match self.read_usage_history() {
+            Ok(history) => self
+                .send
+                .send(MessageToFrontend::UsageHistoryLoaded { history }),
+            Err(e) => self
+                .send
+                .send_error(format!("Error reading usage history: '{}'", e)),
+        }
+    }
+
+    /// Adds exported boards to the usage history, blank cells are left out.
+    pub fn record_usage(&self, boards: Vec<(String, Vec<String>)>) -> Result<()> {
+        let mut history = self.read_usage_history()?;
+        let exported_at = UsageHistory::now();
+
+        history
+            .boards
+            .extend(boards.into_iter().map(|(file, goals)| {
+                HistoryEntry {
+                    exported_at,
+                    file,
+                    goals: goals
+                        .into_iter()
+                        .map(|g| g.trim().to_owned())
+                        .filter(|g| !g.is_empty())
+                        .collect(),
+                }
+            }));
+
+        std::fs::write(
+            self.export_dir.join(HISTORY_FILE),
+            serde_json::to_string_pretty(&history)?,
+        )?;
+        self.send
+            .send(MessageToFrontend::UsageHistoryLoaded { history });
+
+        Ok(())
+    }
+}
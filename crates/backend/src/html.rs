@@ -0,0 +1,139 @@
+use std::{fmt::Write, path::Path};
+
+use anyhow::{Result, bail};
+use bridge::image::BoardImage;
+use chrono::Local;
+
+use crate::{backend::BackendState, image::escape};
+
+const STYLE: &str = r#"
+body { margin: 0; padding: 24px; font-family: "DejaVu Sans", Verdana, sans-serif; background: #f4f4f5; color: #111111; }
+h1 { text-align: center; font-size: 28px; margin: 0 0 16px; }
+.board-wrap { position: relative; margin: 0 auto; max-width: min(96vw, 96vh); }
+.board { display: grid; gap: 4px; background: #222222; border: 4px solid #222222; }
+.cell { aspect-ratio: 1; display: flex; align-items: center; justify-content: center; text-align: center; padding: 6px; background: #ffffff; font-size: clamp(10px, 1.6vw, 18px); overflow-wrap: anywhere; cursor: pointer; user-select: none; }
+.cell.mark-1 { background: #86efac; }
+.cell.mark-2 { background: #fca5a5; }
+.cell.mark-3 { background: #93c5fd; }
+.cell.mark-4 { background: #fde047; }
+.spoiler { position: absolute; inset: 0; display: flex; align-items: center; justify-content: center; background: #222222; color: #ffffff; font-size: 24px; cursor: pointer; }
+.controls { text-align: center; margin-top: 16px; }
+button { font: inherit; padding: 6px 16px; }
+@media print {
+  body { background: #ffffff; padding: 0; }
+  .controls, .spoiler { display: none; }
+  .board-wrap { max-width: 100%; }
+  .cell { cursor: default; font-size: 12pt; }
+  .board, .cell { -webkit-print-color-adjust: exact; print-color-adjust: exact; }
+}
+"#;
+
+const SCRIPT: &str = r#"
+const MARKS = 4;
+const mark = (cell, step) => {
+  const current = Number(cell.dataset.mark || 0);
+  const next = (current + step + MARKS + 1) % (MARKS + 1);
+  cell.classList.remove("mark-" + current);
+  if (next > 0) cell.classList.add("mark-" + next);
+  cell.dataset.mark = next;
+};
+document.querySelectorAll(".cell").forEach((cell) => {
+  cell.addEventListener("click", () => mark(cell, 1));
+  cell.addEventListener("contextmenu", (e) => { e.preventDefault(); mark(cell, -1); });
+});
+document.getElementById("reset").addEventListener("click", () => {
+  document.querySelectorAll(".cell").forEach((cell) => mark(cell, -Number(cell.dataset.mark || 0)));
+});
+const spoiler = document.getElementById("spoiler");
+if (spoiler) spoiler.addEventListener("click", () => spoiler.remove());
+"#;
+
+impl BackendState {
+    /// Writes the board as a single offline HTML page in the export folder, returns the file name.
+    pub async fn export_html(&self, board: BoardImage, spoiler: bool) -> Result<String> {
+        let date = Local::now();
+        let filename = format!("{}_board.html", date.format("%Y-%m-%d_%H-%M-%S"));
+        write_html(&self.export_dir.join(&filename), &board, spoiler)?;
+
+        Ok(filename)
+    }
+}
+
+pub fn write_html(path: &Path, board: &BoardImage, spoiler: bool) -> Result<()> {
+    if board.cells.len() != board.grid_size.cell_count() {
+        bail!(
+            "Board has {} cells, a {}x{} board needs {}",
+            board.cells.len(),
+            board.grid_size.side(),
+            board.grid_size.side(),
+            board.grid_size.cell_count()
+        );
+    }
+
+    std::fs::write(path, render_html(board, spoiler))?;
+
+    Ok(())
+}
+
+/// Page with everything inline, clicking a cell cycles its mark color, right click goes back.
+pub fn render_html(board: &BoardImage, spoiler: bool) -> String {
+    let title = board.title.trim();
+    let page_title = if title.is_empty() { "Bingo" } else { title };
+
+    let mut html = String::new();
+
+    _ = writeln!(html, "<!DOCTYPE html>");
+    _ = writeln!(html, r#"<html lang="en">"#);
+    _ = writeln!(html, "<head>");
+    _ = writeln!(html, r#"<meta charset="utf-8">"#);
+    _ = writeln!(
+        html,
+        r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#
+    );
+    _ = writeln!(html, "<title>{}</title>", escape(page_title));
+    _ = writeln!(html, "<style>{STYLE}</style>");
+    _ = writeln!(html, "</head>");
+    _ = writeln!(html, "<body>");
+
+    if !title.is_empty() {
+        _ = writeln!(html, "<h1>{}</h1>", escape(title));
+    }
+
+    _ = writeln!(html, r#"<div class="board-wrap">"#);
+    _ = writeln!(
+        html,
+        r#"<div class="board" style="grid-template-columns: repeat({}, 1fr);">"#,
+        board.grid_size.side()
+    );
+    for cell in &board.cells {
+        let tooltip = cell
+            .category
+            .as_deref()
+            .map(|c| format!(r#" title="{}""#, escape(c)))
+            .unwrap_or_default();
+        _ = writeln!(
+            html,
+            r#"<div class="cell"{tooltip}>{}</div>"#,
+            escape(cell.text.trim())
+        );
+    }
+    _ = writeln!(html, "</div>");
+
+    if spoiler {
+        _ = writeln!(
+            html,
+            r#"<div class="spoiler" id="spoiler">Click to reveal the board</div>"#
+        );
+    }
+    _ = writeln!(html, "</div>");
+
+    _ = writeln!(
+        html,
+        r#"<div class="controls"><button id="reset">Clear marks</button></div>"#
+    );
+    _ = writeln!(html, "<script>{SCRIPT}</script>");
+    _ = writeln!(html, "</body>");
+    _ = writeln!(html, "</html>");
+
+    html
+}
@@ -0,0 +1,217 @@
+use std::{fmt::Write, path::Path, sync::Arc};
+
+use anyhow::{Context, Result, bail};
+use bridge::image::{BoardImage, ImageFormat};
+use chrono::Local;
+use resvg::{tiny_skia, usvg};
+
+use crate::{
+    backend::BackendState,
+    text::{self, FONT, FONT_FAMILY, LINE_HEIGHT},
+};
+
+const CELL_SIZE: f32 = 160.0;
+const MARGIN: f32 = 24.0;
+const CELL_PADDING: f32 = 10.0;
+const TITLE_SIZE: f32 = 32.0;
+const LEGEND_SIZE: f32 = 16.0;
+const MAX_CELL_FONT_SIZE: f32 = 22.0;
+const MIN_CELL_FONT_SIZE: f32 = 9.0;
+
+/// Light backgrounds dark text stays readable on, handed out to categories in order.
+const CATEGORY_COLORS: [&str; 10] = [
+    "#fde2e4", "#dbeafe", "#dcfce7", "#fef3c7", "#ede9fe", "#cffafe", "#ffedd5", "#fce7f3",
+    "#e5e7eb", "#ecfccb",
+];
+
+impl BackendState {
+    /// Draws the board into an image in the export folder, returns the file name.
+    pub async fn export_image(&self, image: BoardImage, format: ImageFormat) -> Result<String> {
+        let date = Local::now();
+        let filename = format!(
+            "{}_board.{}",
+            date.format("%Y-%m-%d_%H-%M-%S"),
+            format.extension()
+        );
+        write_image(&self.export_dir.join(&filename), &image, format)?;
+
+        Ok(filename)
+    }
+}
+
+pub fn write_image(path: &Path, image: &BoardImage, format: ImageFormat) -> Result<()> {
+    if image.cells.len() != image.grid_size.cell_count() {
+        bail!(
+            "Board has {} cells, a {}x{} board needs {}",
+            image.cells.len(),
+            image.grid_size.side(),
+            image.grid_size.side(),
+            image.grid_size.cell_count()
+        );
+    }
+
+    let svg = render_svg(image);
+    match format {
+        ImageFormat::Svg => std::fs::write(path, svg)?,
+        ImageFormat::Png => std::fs::write(path, render_png(&svg)?)?,
+    }
+
+    Ok(())
+}
+
+/// Rasterizes on the CPU, only the embedded font is used so no system fonts are needed.
+pub fn render_png(svg: &str) -> Result<Vec<u8>> {
+    let mut fontdb = usvg::fontdb::Database::new();
+    fontdb.load_font_data(FONT.to_vec());
+
+    let options = usvg::Options {
+        font_family: FONT_FAMILY.to_owned(),
+        fontdb: Arc::new(fontdb),
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)?;
+
+    let size = tree.size().to_int_size();
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).context("Board image has no size")?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+
+    Ok(pixmap.encode_png()?)
+}
+
+pub fn render_svg(image: &BoardImage) -> String {
+    let side = image.grid_size.side();
+    let grid = side as f32 * CELL_SIZE;
+
+    let mut categories: Vec<&str> = Vec::new();
+    for category in image.cells.iter().filter_map(|c| c.category.as_deref()) {
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    let color = |category: &str| {
+        let idx = categories.iter().position(|c| *c == category).unwrap_or(0);
+        CATEGORY_COLORS[idx % CATEGORY_COLORS.len()]
+    };
+
+    let title = image.title.trim();
+    let title_height = if title.is_empty() {
+        0.0
+    } else {
+        TITLE_SIZE * LINE_HEIGHT + MARGIN / 2.0
+    };
+    let legend_height = if categories.is_empty() {
+        0.0
+    } else {
+        LEGEND_SIZE * LINE_HEIGHT * legend_rows(&categories, grid) as f32 + MARGIN / 2.0
+    };
+    let width = grid + 2.0 * MARGIN;
+    let height = title_height + grid + legend_height + 2.0 * MARGIN;
+    let top = MARGIN + title_height;
+
+    let mut svg = String::new();
+
+    _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="{FONT_FAMILY}, sans-serif">"#
+    );
+    _ = writeln!(
+        svg,
+        r##"<rect width="{width}" height="{height}" fill="#ffffff"/>"##
+    );
+
+    if !title.is_empty() {
+        _ = writeln!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="{TITLE_SIZE}" font-weight="bold" text-anchor="middle" fill="#111111">{}</text>"##,
+            width / 2.0,
+            MARGIN + TITLE_SIZE,
+            escape(title)
+        );
+    }
+
+    for (idx, cell) in image.cells.iter().enumerate() {
+        let x = MARGIN + (idx % side) as f32 * CELL_SIZE;
+        let y = top + (idx / side) as f32 * CELL_SIZE;
+        let fill = cell.category.as_deref().map(color).unwrap_or("#ffffff");
+        _ = writeln!(
+            svg,
+            r##"<rect x="{x}" y="{y}" width="{CELL_SIZE}" height="{CELL_SIZE}" fill="{fill}" stroke="#222222" stroke-width="2"/>"##
+        );
+
+        let inner = CELL_SIZE - 2.0 * CELL_PADDING;
+        let (size, lines) = text::fit_text(
+            cell.text.trim(),
+            inner,
+            inner,
+            MAX_CELL_FONT_SIZE,
+            MIN_CELL_FONT_SIZE,
+        );
+        let line_height = size * LINE_HEIGHT;
+        let first_baseline = y + (CELL_SIZE - line_height * lines.len() as f32) / 2.0 + size * 0.9;
+        for (line_idx, line) in lines.iter().enumerate() {
+            _ = writeln!(
+                svg,
+                r##"<text x="{}" y="{}" font-size="{size}" text-anchor="middle" fill="#111111">{}</text>"##,
+                x + CELL_SIZE / 2.0,
+                first_baseline + line_idx as f32 * line_height,
+                escape(line)
+            );
+        }
+    }
+
+    let swatch = LEGEND_SIZE * 0.8;
+    let (mut x, mut y) = (MARGIN, top + grid + MARGIN / 2.0);
+    for category in &categories {
+        let entry_width = legend_entry_width(category);
+        if x > MARGIN && x + entry_width > MARGIN + grid {
+            x = MARGIN;
+            y += LEGEND_SIZE * LINE_HEIGHT;
+        }
+
+        _ = writeln!(
+            svg,
+            r##"<rect x="{x}" y="{}" width="{swatch}" height="{swatch}" fill="{}" stroke="#222222" stroke-width="1"/>"##,
+            y + (LEGEND_SIZE * LINE_HEIGHT - swatch) / 2.0,
+            color(category)
+        );
+        _ = writeln!(
+            svg,
+            r##"<text x="{}" y="{}" font-size="{LEGEND_SIZE}" fill="#111111">{}</text>"##,
+            x + swatch + LEGEND_SIZE / 2.0,
+            y + LEGEND_SIZE,
+            escape(category)
+        );
+        x += entry_width;
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn legend_entry_width(category: &str) -> f32 {
+    LEGEND_SIZE * 2.5 + text::text_width(category, LEGEND_SIZE)
+}
+
+fn legend_rows(categories: &[&str], width: f32) -> usize {
+    let mut rows = 1;
+    let mut x = 0.0;
+    for category in categories {
+        let entry_width = legend_entry_width(category);
+        if x > 0.0 && x + entry_width > width {
+            rows += 1;
+            x = 0.0;
+        }
+        x += entry_width;
+    }
+
+    rows
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
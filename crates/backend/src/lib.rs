@@ -1,5 +1,19 @@
 mod backend;
 pub mod backend_handler;
+pub mod bulk;
+pub mod caller;
 pub mod export;
+pub mod generator;
+pub mod history;
+pub mod html;
+pub mod image;
+pub mod metadata;
+pub mod pdf;
+pub mod pool;
+pub mod project;
+pub mod solver;
+pub mod table;
+mod text;
+pub mod tournament;
 
 pub use backend::*;
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bridge::metadata::ExportMetadata;
+use chrono::Local;
+use sha2::{Digest, Sha256};
+
+use crate::{backend::BackendState, generator, pool};
+
+const SIDECAR_EXTENSION: &str = ".meta.json";
+
+pub struct VerifyReport {
+    pub export_file: String,
+    /// One line per cell that doesn't match.
+    pub differences: Vec<String>,
+    /// Generator version the export was made with, when it isn't this one.
+    pub other_version: Option<String>,
+}
+
+impl BackendState {
+    pub fn write_sidecar(&self, export_file: &str, mut metadata: ExportMetadata) -> Result<String> {
+        metadata.generator_version = env!("CARGO_PKG_VERSION").to_owned();
+        metadata.exported_at = Local::now().to_rfc3339();
+        metadata.export_file = export_file.to_owned();
+        metadata.pool_hash = metadata
+            .pool_path
+            .as_deref()
+            .and_then(|path| hash_file(path).ok());
+
+        let filename = format!("{export_file}{SIDECAR_EXTENSION}");
+        std::fs::write(
+            self.export_dir.join(filename.clone()),
+            serde_json::to_string_pretty(&metadata)?,
+        )?;
+
+        Ok(filename)
+    }
+}
+
+/// Regenerates the board from an export's sidecar and compares it cell by cell with the export,
+/// `path` can be either of the two files.
+pub fn verify_export(path: &Path) -> Result<VerifyReport> {
+    let (export_path, sidecar_path) = match path.to_str() {
+        Some(p) if p.ends_with(SIDECAR_EXTENSION) => (
+            PathBuf::from(&p[..p.len() - SIDECAR_EXTENSION.len()]),
+            path.to_owned(),
+        ),
+        _ => {
+            let mut sidecar = path.as_os_str().to_owned();
+            sidecar.push(SIDECAR_EXTENSION);
+            (path.to_owned(), PathBuf::from(sidecar))
+        }
+    };
+
+    let data = std::fs::read_to_string(&sidecar_path)
+        .with_context(|| format!("Couldn't read sidecar '{}'", sidecar_path.display()))?;
+    let metadata: ExportMetadata = serde_json::from_str(&data)?;
+
+    let Some(options) = metadata.options() else {
+        bail!("Export is a hand-made board, there's no seed to regenerate it from");
+    };
+    if metadata.rerolled {
+        bail!("Cells were rerolled after generating, the seed alone can't regenerate the board");
+    }
+    if metadata.avoided_recent {
+        bail!("Recently used goals were avoided, the seed alone can't regenerate the board");
+    }
+    let Some(pool_path) = &metadata.pool_path else {
+        bail!("Sidecar doesn't say which goal pool the board was generated from");
+    };
+
+    if let Some(expected) = &metadata.pool_hash
+        && hash_file(pool_path)? != *expected
+    {
+        bail!(
+            "Goal pool '{}' changed since the export, its hash doesn't match",
+            pool_path.display()
+        );
+    }
+
+    let pool = pool::read_goal_pool(pool_path)?;
+    let board = generator::generate(&pool, &options)?;
+    let exported = exported_goals(&export_path)?;
+
+    let mut differences: Vec<String> = board
+        .goals
+        .iter()
+        .zip(exported.iter())
+        .enumerate()
+        .filter(|(_, (goal, exported))| goal.name.trim() != exported.trim())
+        .map(|(idx, (goal, exported))| {
+            format!(
+                "Cell {}: exported '{}', regenerated '{}'",
+                idx + 1,
+                exported.trim(),
+                goal.name.trim()
+            )
+        })
+        .collect();
+    if exported.len() != board.goals.len() {
+        differences.push(format!(
+            "Export has {} goals, regenerated board has {}",
+            exported.len(),
+            board.goals.len()
+        ));
+    }
+
+    Ok(VerifyReport {
+        export_file: export_path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        differences,
+        other_version: (metadata.generator_version != env!("CARGO_PKG_VERSION"))
+            .then_some(metadata.generator_version),
+    })
+}
+
+fn hash_file(path: &Path) -> Result<String> {
+    let data = std::fs::read(path)?;
+
+    Ok(format!("{:x}", Sha256::digest(&data)))
+}
+
+/// Goal texts of a Bingosync or Lockout Live board export, row-major.
+fn exported_goals(path: &Path) -> Result<Vec<String>> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read export '{}'", path.display()))?;
+    let value: serde_json::Value = serde_json::from_str(&data)?;
+
+    if let Some(cards) = value.as_array() {
+        // Small boards are padded with blank cards to fill Bingosync's 5x5 grid.
+        return Ok(cards
+            .iter()
+            .filter_map(|card| card["name"].as_str())
+            .filter(|name| !name.trim().is_empty())
+            .map(str::to_owned)
+            .collect());
+    }
+
+    let Some(objectives) = value["objectives"].as_array() else {
+        bail!("'{}' isn't a board export", path.display());
+    };
+
+    let mut objectives: Vec<(u64, String)> = objectives
+        .iter()
+        .filter_map(|card| {
+            let position = card["forced_positions"][0].as_u64()?;
+            Some((position, card["goal"].as_str()?.to_owned()))
+        })
+        .collect();
+    objectives.sort_by_key(|(position, _)| *position);
+
+    Ok(objectives.into_iter().map(|(_, goal)| goal).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_boards_the_seed_cant_regenerate() {
+        let (state, _recv) = BackendState::for_test("metadata");
+        let metadata = ExportMetadata {
+            seed: Some(1),
+            pool_path: Some(state.export_dir.join("pool.json")),
+            ..Default::default()
+        };
+
+        for (file, metadata, error) in [
+            (
+                "rerolled.json",
+                ExportMetadata {
+                    rerolled: true,
+                    ..metadata.clone()
+                },
+                "Cells were rerolled",
+            ),
+            (
+                "avoided.json",
+                ExportMetadata {
+                    avoided_recent: true,
+                    ..metadata.clone()
+                },
+                "Recently used goals were avoided",
+            ),
+        ] {
+            let sidecar = state.write_sidecar(file, metadata).unwrap();
+            let Err(e) = verify_export(&state.export_dir.join(sidecar)) else {
+                panic!("{file} was verified");
+            };
+            assert!(e.to_string().starts_with(error), "{file}: {e}");
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use std::{collections::HashSet, fs::File, io::BufWriter, path::Path};
+
+use anyhow::{Context, Result, bail};
+use bridge::{
+    board::{GenerateOptions, GeneratedBoard},
+    caller::{CallerGame, CardSet, FREE_SPACE},
+    goal::{Goal, GoalPool},
+    print::PrintOptions,
+};
+use chrono::Local;
+use printpdf::{
+    Color, Greyscale, IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point,
+};
+
+use crate::{
+    backend::BackendState,
+    bulk::Progress,
+    generator,
+    text::{self, FONT, LINE_HEIGHT},
+};
+
+/// Seeds tried per card before giving up on finding one that isn't a duplicate.
+const DUPLICATE_ATTEMPTS: usize = 20;
+
+const PAGE_MARGIN: f32 = 12.0;
+const CARD_GAP: f32 = 10.0;
+const CELL_PADDING: f32 = 1.5;
+const PT_TO_MM: f32 = 0.352_778;
+const MAX_CELL_FONT_SIZE: f32 = 12.0;
+const MIN_CELL_FONT_SIZE: f32 = 5.0;
+const SHEET_FONT_SIZE: f32 = 10.0;
+const SHEET_COLUMNS: usize = 2;
+
+/// Current page being drawn on, along with what every card's header shows.
+struct Printer<'a> {
+    layer: PdfLayerReference,
+    font: IndirectFontRef,
+    page_height: f32,
+    card_set: &'a CardSet,
+}
+
+struct Area {
+    x: f32,
+    /// Top edge, measured from the top of the page.
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl BackendState {
+    /// Lays out `options.count` distinct cards into a printable PDF, returns the file name.
+    pub async fn export_pdf_cards(&self, pool: GoalPool, options: PrintOptions) -> Result<String> {
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || state.write_pdf_cards(pool, options)).await?
+    }
+
+    fn write_pdf_cards(&self, pool: GoalPool, options: PrintOptions) -> Result<String> {
+        let mut progress = Progress::new(self.send.clone(), options.count);
+        let cards = generate_cards(&pool, &options, |done| progress.update(done))?;
+
+        let date = Local::now();
+        let stem = format!("{}_cards", date.format("%Y-%m-%d_%H-%M-%S"));
+        let title = options.title.trim();
+        let card_set = CardSet {
+            generator_version: env!("CARGO_PKG_VERSION").to_owned(),
+            created_at: date.to_rfc3339(),
+            pdf_file: format!("{}.pdf", stem),
+            paper: options.paper,
+            title: if title.is_empty() { "Bingo" } else { title }.to_owned(),
+            game_name: pool.game_name.trim().to_owned(),
+            grid_size: options.generate.grid_size,
+            free_center: options.free_center,
+            cards: cards
+                .iter()
+                .map(|card| {
+                    card.goals
+                        .iter()
+                        .map(|g| g.name.trim().to_owned())
+                        .collect()
+                })
+                .collect(),
+        };
+
+        write_cards(
+            &self.export_dir.join(&card_set.pdf_file),
+            &card_set,
+            options.cards_per_page,
+        )?;
+        // Saved along with the PDF so caller mode can check the cards later.
+        std::fs::write(
+            self.export_dir.join(format!("{}.json", stem)),
+            serde_json::to_string_pretty(&card_set)?,
+        )?;
+
+        Ok(card_set.pdf_file)
+    }
+}
+
+/// Generates cards with consecutive seeds, skipping any with the same layout as an earlier one.
+pub fn generate_cards(
+    pool: &GoalPool,
+    options: &PrintOptions,
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<GeneratedBoard>> {
+    let grid_size = options.generate.grid_size;
+
+    if options.count == 0 {
+        bail!("Printing needs at least one card");
+    }
+    if ![1, 2, 4].contains(&options.cards_per_page) {
+        bail!("Pages can hold 1, 2 or 4 cards");
+    }
+    if options.free_center && grid_size.side().is_multiple_of(2) {
+        bail!(
+            "A {}x{} card has no center cell for a free space",
+            grid_size.side(),
+            grid_size.side()
+        );
+    }
+
+    let mut generate = GenerateOptions {
+        pinned: Default::default(),
+        ..options.generate.clone()
+    };
+    if options.free_center {
+        generate.pinned.insert(
+            grid_size.cell_count() / 2,
+            Goal {
+                name: FREE_SPACE.to_owned(),
+                ..Default::default()
+            },
+        );
+    }
+
+    let first_seed = options.first_seed.unwrap_or_else(rand::random);
+    let mut layouts: HashSet<Vec<String>> = HashSet::new();
+    let mut cards = Vec::with_capacity(options.count);
+    let mut seed = first_seed;
+    let mut attempts = 0;
+
+    while cards.len() < options.count {
+        let card = generator::generate(
+            pool,
+            &GenerateOptions {
+                seed: Some(seed),
+                ..generate.clone()
+            },
+        )
+        .with_context(|| format!("Card {} with seed {}", cards.len() + 1, seed))?;
+        seed = seed.wrapping_add(1);
+
+        let layout = card
+            .goals
+            .iter()
+            .map(|g| g.name.trim().to_owned())
+            .collect();
+        if !layouts.insert(layout) {
+            attempts += 1;
+            if attempts == DUPLICATE_ATTEMPTS {
+                bail!(
+                    "Goal pool only has room for {} distinct cards of this size",
+                    cards.len()
+                );
+            }
+            continue;
+        }
+
+        attempts = 0;
+        cards.push(card);
+        progress(cards.len());
+    }
+
+    Ok(cards)
+}
+
+fn write_cards(path: &Path, card_set: &CardSet, cards_per_page: usize) -> Result<()> {
+    let (page_width, page_height) = card_set.paper.size_mm();
+    let (doc, page, layer) = PdfDocument::new(
+        card_set.title.as_str(),
+        Mm(page_width),
+        Mm(page_height),
+        "Cards",
+    );
+    let font = doc.add_external_font(FONT)?;
+
+    let areas = card_areas(page_width, page_height, cards_per_page);
+    let mut printer = Printer {
+        layer: doc.get_page(page).get_layer(layer),
+        font,
+        page_height,
+        card_set,
+    };
+
+    for (idx, cells) in card_set.cards.iter().enumerate() {
+        let slot = idx % cards_per_page;
+        if idx > 0 && slot == 0 {
+            let (page, layer) = doc.add_page(Mm(page_width), Mm(page_height), "Cards");
+            printer.layer = doc.get_page(page).get_layer(layer);
+        }
+
+        printer.draw_card(&areas[slot], idx + 1, cells);
+    }
+
+    doc.save(&mut BufWriter::new(File::create(path)?))?;
+
+    Ok(())
+}
+
+/// Call order of a game as a numbered list with boxes to tick, goals called so far are crossed.
+pub fn write_caller_sheet(path: &Path, card_set: &CardSet, game: &CallerGame) -> Result<()> {
+    let (page_width, page_height) = card_set.paper.size_mm();
+    let title = format!("{} - Caller Sheet", card_set.title);
+    let (doc, page, layer) =
+        PdfDocument::new(title.as_str(), Mm(page_width), Mm(page_height), "Sheet");
+    let font = doc.add_external_font(FONT)?;
+    let mut layer = doc.get_page(page).get_layer(layer);
+    let to_pdf = |y: f32| Mm(page_height - y);
+
+    let line_height = SHEET_FONT_SIZE * LINE_HEIGHT * PT_TO_MM * 1.4;
+    let column_width = (page_width - 2.0 * PAGE_MARGIN) / SHEET_COLUMNS as f32;
+    let header_height = 20.0 * LINE_HEIGHT * PT_TO_MM + SHEET_FONT_SIZE * LINE_HEIGHT * PT_TO_MM;
+    let rows = ((page_height - 2.0 * PAGE_MARGIN - header_height) / line_height) as usize;
+    let per_page = rows * SHEET_COLUMNS;
+    let box_size = SHEET_FONT_SIZE * PT_TO_MM;
+
+    layer.set_outline_color(Color::Greyscale(Greyscale::new(0.0, None)));
+    layer.set_outline_thickness(0.5);
+
+    for (idx, goal) in game.order.iter().enumerate() {
+        let slot = idx % per_page;
+        if slot == 0 {
+            if idx > 0 {
+                let (page, new_layer) = doc.add_page(Mm(page_width), Mm(page_height), "Sheet");
+                layer = doc.get_page(page).get_layer(new_layer);
+                layer.set_outline_color(Color::Greyscale(Greyscale::new(0.0, None)));
+                layer.set_outline_thickness(0.5);
+            }
+
+            let title_y = PAGE_MARGIN + 20.0 * PT_TO_MM;
+            layer.use_text(
+                title.as_str(),
+                20.0,
+                Mm(PAGE_MARGIN),
+                to_pdf(title_y),
+                &font,
+            );
+            layer.use_text(
+                format!(
+                    "{}, seed {}, {} goals, {} cards",
+                    card_set.game_name,
+                    game.seed,
+                    game.order.len(),
+                    card_set.cards.len()
+                )
+                .trim_start_matches(", "),
+                SHEET_FONT_SIZE,
+                Mm(PAGE_MARGIN),
+                to_pdf(title_y + SHEET_FONT_SIZE * LINE_HEIGHT * PT_TO_MM * 1.5),
+                &font,
+            );
+        }
+
+        let (column, row) = (slot / rows, slot % rows);
+        let x = PAGE_MARGIN + column as f32 * column_width;
+        let baseline = PAGE_MARGIN + header_height + (row + 1) as f32 * line_height;
+
+        let box_top = baseline - box_size;
+        let corners = [
+            (x, box_top),
+            (x + box_size, box_top),
+            (x + box_size, baseline),
+            (x, baseline),
+        ];
+        for (from, to) in corners.iter().zip(corners.iter().cycle().skip(1)) {
+            layer.add_line(line(*from, *to, to_pdf));
+        }
+        if idx < game.called {
+            layer.add_line(line(corners[0], corners[2], to_pdf));
+            layer.add_line(line(corners[1], corners[3], to_pdf));
+        }
+
+        let number = format!("{}.", idx + 1);
+        let text_x = x + box_size + 2.0;
+        layer.use_text(
+            number.as_str(),
+            SHEET_FONT_SIZE,
+            Mm(text_x),
+            to_pdf(baseline),
+            &font,
+        );
+
+        let goal_x = text_x + text_width("0000", SHEET_FONT_SIZE);
+        let room = column_width - (goal_x - x) - 3.0;
+        let size = text::fit_line(goal, room / PT_TO_MM, SHEET_FONT_SIZE, MIN_CELL_FONT_SIZE);
+        layer.use_text(goal.as_str(), size, Mm(goal_x), to_pdf(baseline), &font);
+    }
+
+    doc.save(&mut BufWriter::new(File::create(path)?))?;
+
+    Ok(())
+}
+
+/// Where each card of a page goes, one full page, two stacked or two by two.
+fn card_areas(page_width: f32, page_height: f32, cards_per_page: usize) -> Vec<Area> {
+    let (columns, rows) = match cards_per_page {
+        1 => (1, 1),
+        2 => (1, 2),
+        _ => (2, 2),
+    };
+    let width = (page_width - 2.0 * PAGE_MARGIN - (columns - 1) as f32 * CARD_GAP) / columns as f32;
+    let height = (page_height - 2.0 * PAGE_MARGIN - (rows - 1) as f32 * CARD_GAP) / rows as f32;
+
+    (0..rows)
+        .flat_map(|row| {
+            (0..columns).map(move |column| Area {
+                x: PAGE_MARGIN + column as f32 * (width + CARD_GAP),
+                y: PAGE_MARGIN + row as f32 * (height + CARD_GAP),
+                width,
+                height,
+            })
+        })
+        .collect()
+}
+
+impl Printer<'_> {
+    fn draw_card(&self, area: &Area, number: usize, cells: &[String]) {
+        let (layer, font) = (&self.layer, &self.font);
+        let page_height = self.page_height;
+
+        // Everything scales with the card, a full A4 page is the reference.
+        let scale = (area.width / 186.0).min(1.0);
+        let title_size = 20.0 * scale;
+        let subtitle_size = 11.0 * scale;
+        let header_height = (title_size + subtitle_size) * LINE_HEIGHT * PT_TO_MM + 4.0 * scale;
+        let to_pdf = |y: f32| Mm(page_height - y);
+
+        let title_y = area.y + title_size * PT_TO_MM;
+        layer.use_text(
+            self.card_set.title.as_str(),
+            title_size,
+            Mm(area.x),
+            to_pdf(title_y),
+            font,
+        );
+
+        let number_text = format!("Card {}", number);
+        layer.use_text(
+            number_text.as_str(),
+            subtitle_size,
+            Mm(area.x + area.width - text_width(&number_text, subtitle_size)),
+            to_pdf(title_y),
+            font,
+        );
+        if !self.card_set.game_name.is_empty() {
+            layer.use_text(
+                self.card_set.game_name.as_str(),
+                subtitle_size,
+                Mm(area.x),
+                to_pdf(title_y + subtitle_size * LINE_HEIGHT * PT_TO_MM),
+                font,
+            );
+        }
+
+        let side = self.card_set.grid_size.side();
+        let grid = area.width.min(area.height - header_height);
+        let cell = grid / side as f32;
+        let left = area.x + (area.width - grid) / 2.0;
+        let top = area.y + header_height;
+
+        layer.set_outline_color(Color::Greyscale(Greyscale::new(0.0, None)));
+        layer.set_outline_thickness(0.8 * scale);
+        for i in 0..=side {
+            let offset = i as f32 * cell;
+            layer.add_line(line(
+                (left + offset, top),
+                (left + offset, top + grid),
+                to_pdf,
+            ));
+            layer.add_line(line(
+                (left, top + offset),
+                (left + grid, top + offset),
+                to_pdf,
+            ));
+        }
+
+        for (idx, text) in cells.iter().enumerate() {
+            let (x, y) = (idx % side, idx / side);
+            let inner = cell - 2.0 * CELL_PADDING;
+            let (size, lines) = if self.card_set.free_center && text == FREE_SPACE {
+                (MAX_CELL_FONT_SIZE * 1.5 * scale, vec![text.to_owned()])
+            } else {
+                text::fit_text(
+                    text,
+                    inner / PT_TO_MM,
+                    inner / PT_TO_MM,
+                    MAX_CELL_FONT_SIZE,
+                    MIN_CELL_FONT_SIZE,
+                )
+            };
+
+            let line_height = size * LINE_HEIGHT * PT_TO_MM;
+            let block_top = top + y as f32 * cell + (cell - line_height * lines.len() as f32) / 2.0;
+            for (line_idx, text) in lines.iter().enumerate() {
+                let baseline = block_top + line_height * line_idx as f32 + size * PT_TO_MM;
+                let start = left + x as f32 * cell + (cell - text_width(text, size)) / 2.0;
+                layer.use_text(text.as_str(), size, Mm(start), to_pdf(baseline), font);
+            }
+        }
+    }
+}
+
+fn line(from: (f32, f32), to: (f32, f32), to_pdf: impl Fn(f32) -> Mm) -> Line {
+    Line {
+        points: vec![
+            (Point::new(Mm(from.0), to_pdf(from.1)), false),
+            (Point::new(Mm(to.0), to_pdf(to.1)), false),
+        ],
+        is_closed: false,
+    }
+}
+
+/// Width in millimeters of text at a font size in points.
+fn text_width(text: &str, size: f32) -> f32 {
+    text::text_width(text, size) * PT_TO_MM
+}
+
+#[cfg(test)]
+mod tests {
+    use bridge::board::GridSize;
+
+    use super::*;
+
+    fn options(grid_size: GridSize, count: usize) -> PrintOptions {
+        PrintOptions {
+            count,
+            first_seed: Some(0),
+            generate: GenerateOptions {
+                grid_size,
+                ..Default::default()
+            },
+            cards_per_page: 4,
+            free_center: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn cards_are_distinct_with_free_center() {
+        let pool = GoalPool {
+            goals: (0..10)
+                .map(|i| Goal {
+                    name: format!("Goal {i}"),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        let mut done = 0;
+        let cards = generate_cards(&pool, &options(GridSize::Size3, 50), |d| done = d).unwrap();
+        assert_eq!(cards.len(), 50);
+        assert_eq!(done, 50);
+
+        let layouts: HashSet<Vec<&str>> = cards
+            .iter()
+            .map(|card| card.goals.iter().map(|g| g.name.as_str()).collect())
+            .collect();
+        assert_eq!(layouts.len(), 50);
+
+        for card in &cards {
+            let free: Vec<usize> = card
+                .goals
+                .iter()
+                .enumerate()
+                .filter(|(_, g)| g.name == FREE_SPACE)
+                .map(|(idx, _)| idx)
+                .collect();
+            assert_eq!(free, [4]);
+        }
+    }
+
+    #[test]
+    fn free_center_needs_odd_size() {
+        let error =
+            generate_cards(&GoalPool::default(), &options(GridSize::Size4, 1), |_| {}).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "A 4x4 card has no center cell for a free space"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use bridge::{
+    goal::GoalPool,
+    goal_list::{self, GoalList},
+};
+
+use crate::backend::BackendState;
+
+/// Unparsed lines listed in a warning before the rest are only counted.
+const LISTED_LINE_ERRORS: usize = 5;
+
+impl BackendState {
+    pub async fn load_goal_pool(&self, path: &Path) -> Result<GoalPool> {
+        read_goal_pool(path)
+    }
+
+    /// Goal pool from a plain text or Markdown list, skipped lines are reported in a warning.
+    pub async fn import_goal_list(&self, path: &Path) -> Result<GoalPool> {
+        let GoalList { pool, errors } = read_goal_list(path)?;
+
+        let mut listed: Vec<String> = errors
+            .iter()
+            .take(LISTED_LINE_ERRORS)
+            .map(|e| e.to_string())
+            .collect();
+        if errors.len() > LISTED_LINE_ERRORS {
+            listed.push(format!("{} more", errors.len() - LISTED_LINE_ERRORS));
+        }
+
+        if pool.goals.is_empty() {
+            bail!(
+                "No goals found in '{}'. {}",
+                path.display(),
+                listed.join(", ")
+            );
+        }
+        if !errors.is_empty() {
+            self.send.send_warning(format!(
+                "Skipped {} lines: {}",
+                errors.len(),
+                listed.join(", ")
+            ));
+        }
+
+        Ok(pool)
+    }
+}
+
+pub fn read_goal_pool(path: &Path) -> Result<GoalPool> {
+    let data = std::fs::read_to_string(path)?;
+    let pool: GoalPool = serde_json::from_str(&data)?;
+
+    Ok(pool)
+}
+
+/// Named after the file when the list has no heading.
+pub fn read_goal_list(path: &Path) -> Result<GoalList> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read '{}'", path.display()))?;
+
+    let mut list = goal_list::parse_goal_list(&text);
+    if list.pool.game_name.is_empty() {
+        list.pool.game_name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+    }
+
+    Ok(list)
+}
@@ -0,0 +1,214 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use bridge::project::{BingoProject, PROJECT_EXTENSION, PROJECT_SCHEMA_VERSION};
+use chrono::Local;
+use serde_json::Value;
+
+use crate::backend::BackendState;
+
+/// Upgrades a project saved with schema version `n + 1` to `n + 2`, in place.
+type Migration = fn(&mut Value) -> Result<()>;
+
+/// One entry per schema version after the first, in order.
+const MIGRATIONS: &[Migration] = &[];
+const _: () = assert!(MIGRATIONS.len() == PROJECT_SCHEMA_VERSION as usize - 1);
+
+impl BackendState {
+    pub async fn open_project(&self, path: &Path) -> Result<BingoProject> {
+        read_project(path)
+    }
+
+    /// Saves over `path`, or into the export folder when the project has no file yet. Returns
+    /// where it was saved.
+    pub async fn save_project(
+        &self,
+        path: Option<PathBuf>,
+        project: BingoProject,
+    ) -> Result<PathBuf> {
+        let path = path.unwrap_or_else(|| {
+            self.export_dir.join(format!(
+                "{}_board.{}",
+                Local::now().format("%Y-%m-%d_%H-%M-%S"),
+                PROJECT_EXTENSION
+            ))
+        });
+        write_project(&path, project)?;
+
+        Ok(path)
+    }
+}
+
+pub fn read_project(path: &Path) -> Result<BingoProject> {
+    let data = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read '{}'", path.display()))?;
+    let mut value: Value = serde_json::from_str(&data).context("Not a bingo project file")?;
+
+    migrate(&mut value)?;
+    let project: BingoProject =
+        serde_json::from_value(value).context("Project file is damaged or incomplete")?;
+
+    if project.cells.len() != project.grid_size.cell_count() {
+        bail!(
+            "Project has {} cells, a {}x{} board needs {}",
+            project.cells.len(),
+            project.grid_size.side(),
+            project.grid_size.side(),
+            project.grid_size.cell_count()
+        );
+    }
+
+    Ok(project)
+}
+
+pub fn write_project(path: &Path, mut project: BingoProject) -> Result<()> {
+    project.schema_version = PROJECT_SCHEMA_VERSION;
+    project.generator_version = env!("CARGO_PKG_VERSION").to_owned();
+    project.saved_at = Local::now().to_rfc3339();
+
+    std::fs::write(path, serde_json::to_string_pretty(&project)?)?;
+
+    Ok(())
+}
+
+/// Runs every migration from the file's schema version up to the current one.
+fn migrate(value: &mut Value) -> Result<()> {
+    run_migrations(value, MIGRATIONS)
+}
+
+/// Runs `migrations` from the file's schema version on, the last one upgrades to version
+/// `migrations.len() + 1`.
+fn run_migrations(value: &mut Value, migrations: &[Migration]) -> Result<()> {
+    let latest = migrations.len() as u32 + 1;
+    let version = value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .context("Project file has no schema version")? as u32;
+
+    if version == 0 || version > latest {
+        bail!(
+            "Project schema version {} isn't supported, this version reads up to {}",
+            version,
+            latest
+        );
+    }
+
+    for migration in &migrations[version as usize - 1..] {
+        migration(value)?;
+    }
+    value["schema_version"] = latest.into();
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bridge::{board::GridSize, goal::Goal, project::ProjectCell};
+
+    use super::*;
+
+    fn project(grid_size: GridSize, cells: usize) -> BingoProject {
+        BingoProject {
+            game_name: "Game".to_owned(),
+            grid_size,
+            cells: (0..cells)
+                .map(|i| ProjectCell {
+                    goal: Goal {
+                        name: format!("Goal {i}"),
+                        difficulty: i,
+                        ..Default::default()
+                    },
+                    pinned: i == 0,
+                    notes: String::new(),
+                })
+                .collect(),
+            seed: Some(7),
+            notes: "Week 3".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn write_value(path: &Path, value: &Value) {
+        std::fs::write(path, serde_json::to_string(value).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn save_and_open_round_trip() {
+        let (state, _recv) = BackendState::for_test("project-round-trip");
+        let path = state.export_dir.join("board.bingo");
+
+        write_project(&path, project(GridSize::Size3, 9)).unwrap();
+        let opened = read_project(&path).unwrap();
+
+        assert_eq!(opened.schema_version, PROJECT_SCHEMA_VERSION);
+        assert_eq!(opened.generator_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(opened.game_name, "Game");
+        assert_eq!(opened.grid_size, GridSize::Size3);
+        assert_eq!(
+            opened.cell_texts(),
+            project(GridSize::Size3, 9).cell_texts()
+        );
+        assert!(opened.cells[0].pinned && !opened.cells[1].pinned);
+        assert_eq!(opened.cells[4].goal.difficulty, 4);
+        assert_eq!(opened.seed, Some(7));
+        assert_eq!(opened.notes, "Week 3");
+    }
+
+    #[test]
+    fn rejects_unsupported_schema_versions() {
+        let (state, _recv) = BackendState::for_test("project-versions");
+        let path = state.export_dir.join("board.bingo");
+
+        for version in [0, PROJECT_SCHEMA_VERSION + 1] {
+            let mut value = serde_json::to_value(project(GridSize::Size3, 9)).unwrap();
+            value["schema_version"] = version.into();
+            write_value(&path, &value);
+
+            let error = read_project(&path).unwrap_err().to_string();
+            assert!(
+                error.starts_with(&format!("Project schema version {version} isn't supported")),
+                "{error}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_cell_count() {
+        let (state, _recv) = BackendState::for_test("project-cells");
+        let path = state.export_dir.join("board.bingo");
+
+        write_project(&path, project(GridSize::Size3, 8)).unwrap();
+
+        assert_eq!(
+            read_project(&path).unwrap_err().to_string(),
+            "Project has 8 cells, a 3x3 board needs 9"
+        );
+    }
+
+    #[test]
+    fn migrates_older_schema_versions() {
+        // Pretend version 1 called the notes `comment`.
+        fn rename_comment(value: &mut Value) -> Result<()> {
+            let comment = value
+                .as_object_mut()
+                .and_then(|fields| fields.remove("comment"))
+                .context("Version 1 project has no comment")?;
+            value["notes"] = comment;
+            Ok(())
+        }
+        let migrations: &[Migration] = &[rename_comment];
+
+        let mut value = serde_json::to_value(project(GridSize::Size3, 9)).unwrap();
+        value["schema_version"] = 1.into();
+        value["comment"] = value["notes"].take();
+        run_migrations(&mut value, migrations).unwrap();
+
+        let migrated: BingoProject = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(migrated.schema_version, 2);
+        assert_eq!(migrated.notes, "Week 3");
+
+        // Already on version 2, nothing to run.
+        run_migrations(&mut value, migrations).unwrap();
+        assert_eq!(value["notes"], "Week 3");
+    }
+}
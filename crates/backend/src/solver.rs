@@ -0,0 +1,505 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use bridge::{
+    board::{BoardConstraints, GridSize},
+    goal::{Goal, GoalPool},
+    rules,
+};
+use rand::Rng;
+
+use crate::generator::shuffle_weighted;
+
+/// Fresh searches from a reshuffled pool before giving up, a search that keeps backtracking in
+/// the same corner rarely gets out of it.
+const RESTARTS: usize = 10;
+/// Candidate checks a single search may do.
+const STEP_LIMIT: usize = 50_000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    PoolSize,
+    Exclusion,
+    LineRule,
+    BoardMin(String),
+    BoardMax(String),
+    LineMin(String),
+    LineMax(String),
+    LineDifficulty,
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Constraint::PoolSize => write!(f, "pool size"),
+            Constraint::Exclusion => write!(f, "exclusion groups"),
+            Constraint::LineRule => write!(f, "line rules"),
+            Constraint::BoardMin(c) => write!(f, "board minimum of '{c}'"),
+            Constraint::BoardMax(c) => write!(f, "board limit of '{c}'"),
+            Constraint::LineMin(c) => write!(f, "line minimum of '{c}'"),
+            Constraint::LineMax(c) => write!(f, "line limit of '{c}'"),
+            Constraint::LineDifficulty => write!(f, "line difficulty target"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SolveFailure {
+    pub constraint: Constraint,
+    pub filled: usize,
+    pub cells: usize,
+    pub pinned: bool,
+    pub gave_up: bool,
+}
+
+impl fmt::Display for SolveFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pinned {
+            return write!(f, "Pinned cells already break the {}", self.constraint);
+        }
+
+        if self.gave_up {
+            write!(
+                f,
+                "Gave up searching, mostly blocked by the {}",
+                self.constraint
+            )?;
+        } else {
+            write!(f, "Couldn't satisfy the {}", self.constraint)?;
+        }
+
+        write!(
+            f,
+            ", best attempt filled {} of {} cells",
+            self.filled, self.cells
+        )
+    }
+}
+
+impl std::error::Error for SolveFailure {}
+
+struct Search<'a> {
+    pool: &'a GoalPool,
+    constraints: &'a BoardConstraints,
+    side: usize,
+    lines: Vec<Vec<usize>>,
+    cell_lines: Vec<Vec<usize>>,
+    min_difficulty: usize,
+    max_difficulty: usize,
+
+    cells: Vec<Option<&'a Goal>>,
+    used: HashSet<&'a str>,
+    board_counts: HashMap<&'a str, usize>,
+    line_counts: Vec<HashMap<&'a str, usize>>,
+    line_totals: Vec<usize>,
+    line_filled: Vec<usize>,
+
+    steps: usize,
+    best_filled: usize,
+    rejections: HashMap<Constraint, usize>,
+}
+
+/// Fills the board cell by cell, backtracking whenever a goal can't fit, returns the goals
+/// row-major.
+pub fn solve<'a>(
+    pool: &'a GoalPool,
+    constraints: &'a BoardConstraints,
+    grid_size: GridSize,
+    rng: &mut impl Rng,
+) -> Result<Vec<Goal>, SolveFailure> {
+    let lines = grid_size.lines();
+    let mut cell_lines = vec![Vec::new(); grid_size.cell_count()];
+    for (line_idx, line) in lines.iter().enumerate() {
+        for &cell in line {
+            cell_lines[cell].push(line_idx);
+        }
+    }
+
+    let mut search = Search {
+        pool,
+        constraints,
+        side: grid_size.side(),
+        line_counts: vec![HashMap::new(); lines.len()],
+        line_totals: vec![0; lines.len()],
+        line_filled: vec![0; lines.len()],
+        lines,
+        cell_lines,
+        min_difficulty: pool.goals.iter().map(|g| g.difficulty).min().unwrap_or(0),
+        max_difficulty: pool.goals.iter().map(|g| g.difficulty).max().unwrap_or(0),
+        cells: vec![None; grid_size.cell_count()],
+        used: HashSet::new(),
+        board_counts: HashMap::new(),
+        steps: 0,
+        best_filled: 0,
+        rejections: HashMap::new(),
+    };
+
+    let failure = |constraint, filled, pinned, gave_up| SolveFailure {
+        constraint,
+        filled,
+        cells: grid_size.cell_count(),
+        pinned,
+        gave_up,
+    };
+
+    let pool_names: HashSet<&str> = pool
+        .goals
+        .iter()
+        .map(|g| g.name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+    let unique_goals = pool_names.len();
+    // Pinned goals from the pool already count towards it.
+    let pinned_outside = constraints
+        .pinned
+        .values()
+        .filter(|g| !pool_names.contains(g.name.trim()))
+        .count();
+    if unique_goals + pinned_outside < grid_size.cell_count() {
+        return Err(failure(Constraint::PoolSize, unique_goals, false, false));
+    }
+
+    for (&cell, goal) in constraints.pinned.iter() {
+        if cell >= grid_size.cell_count() {
+            continue;
+        }
+        if let Err(constraint) = search.check(goal, cell) {
+            return Err(failure(constraint, 0, true, false));
+        }
+        search.place(goal, cell);
+    }
+
+    let empty: Vec<usize> = (0..grid_size.cell_count())
+        .filter(|&cell| search.cells[cell].is_none())
+        .collect();
+    let mut candidates: Vec<&Goal> = pool
+        .goals
+        .iter()
+        .filter(|g| !g.name.trim().is_empty())
+        .collect();
+
+    let mut gave_up = false;
+    for _ in 0..RESTARTS {
+        search.steps = 0;
+        if search.fill(&empty, &mut candidates, rng) {
+            return Ok(search.cells.into_iter().flatten().cloned().collect());
+        }
+
+        if search.steps < STEP_LIMIT {
+            break;
+        }
+        gave_up = true;
+    }
+
+    let constraint = search
+        .rejections
+        .iter()
+        .max_by_key(|(_, count)| **count)
+        .map(|(c, _)| c.clone())
+        .unwrap_or(Constraint::PoolSize);
+    Err(failure(constraint, search.best_filled, false, gave_up))
+}
+
+impl<'a> Search<'a> {
+    fn fill(&mut self, empty: &[usize], candidates: &mut [&'a Goal], rng: &mut impl Rng) -> bool {
+        let Some((&cell, rest)) = empty.split_first() else {
+            return true;
+        };
+
+        let depth = self.cells.iter().filter(|c| c.is_some()).count();
+        if depth > self.best_filled {
+            self.best_filled = depth;
+            self.rejections.clear();
+        }
+
+        shuffle_weighted(candidates, rng);
+        let order: Vec<&Goal> = candidates.to_vec();
+
+        for goal in order {
+            if self.steps >= STEP_LIMIT {
+                return false;
+            }
+            self.steps += 1;
+
+            if self.used.contains(goal.name.trim()) {
+                continue;
+            }
+
+            if let Err(constraint) = self.check(goal, cell) {
+                if depth == self.best_filled {
+                    *self.rejections.entry(constraint).or_default() += 1;
+                }
+                continue;
+            }
+
+            self.place(goal, cell);
+            if self.fill(rest, candidates, rng) {
+                return true;
+            }
+            self.remove(goal, cell);
+        }
+
+        false
+    }
+
+    fn check(&self, goal: &Goal, cell: usize) -> Result<(), Constraint> {
+        if self.used.contains(goal.name.trim()) {
+            return Err(Constraint::PoolSize);
+        }
+
+        if self
+            .cells
+            .iter()
+            .flatten()
+            .any(|g| rules::excludes(g, goal))
+        {
+            return Err(Constraint::Exclusion);
+        }
+
+        for category in &goal.categories {
+            let count = self
+                .board_counts
+                .get(category.as_str())
+                .copied()
+                .unwrap_or(0)
+                + 1;
+            if self
+                .constraints
+                .board_max
+                .get(category)
+                .is_some_and(|&max| count > max)
+            {
+                return Err(Constraint::BoardMax(category.clone()));
+            }
+        }
+
+        let remaining = self.cells.iter().filter(|c| c.is_none()).count() - 1;
+        for (category, &min) in &self.constraints.board_min {
+            let count = self
+                .board_counts
+                .get(category.as_str())
+                .copied()
+                .unwrap_or(0)
+                + goal.categories.contains(category) as usize;
+            if count + remaining < min {
+                return Err(Constraint::BoardMin(category.clone()));
+            }
+        }
+
+        for &line_idx in &self.cell_lines[cell] {
+            let line = &self.lines[line_idx];
+
+            if line
+                .iter()
+                .filter_map(|&c| self.cells[c])
+                .any(|g| rules::conflicts(self.pool, g, goal))
+            {
+                return Err(Constraint::LineRule);
+            }
+
+            let counts = &self.line_counts[line_idx];
+            for category in &goal.categories {
+                let count = counts.get(category.as_str()).copied().unwrap_or(0) + 1;
+                if self
+                    .constraints
+                    .line_max
+                    .get(category)
+                    .is_some_and(|&max| count > max)
+                {
+                    return Err(Constraint::LineMax(category.clone()));
+                }
+            }
+
+            let remaining = self.side - self.line_filled[line_idx] - 1;
+            for (category, &min) in &self.constraints.line_min {
+                let count = counts.get(category.as_str()).copied().unwrap_or(0)
+                    + goal.categories.contains(category) as usize;
+                if count + remaining < min {
+                    return Err(Constraint::LineMin(category.clone()));
+                }
+            }
+
+            if let Some(target) = self.constraints.line_difficulty {
+                let total = self.line_totals[line_idx] + goal.difficulty;
+                if total + remaining * self.min_difficulty > target.max()
+                    || total + remaining * self.max_difficulty < target.min()
+                {
+                    return Err(Constraint::LineDifficulty);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn place(&mut self, goal: &'a Goal, cell: usize) {
+        self.cells[cell] = Some(goal);
+        self.used.insert(goal.name.trim());
+
+        for category in &goal.categories {
+            *self.board_counts.entry(category.as_str()).or_default() += 1;
+        }
+
+        for &line_idx in &self.cell_lines[cell] {
+            for category in &goal.categories {
+                *self.line_counts[line_idx]
+                    .entry(category.as_str())
+                    .or_default() += 1;
+            }
+            self.line_totals[line_idx] += goal.difficulty;
+            self.line_filled[line_idx] += 1;
+        }
+    }
+
+    fn remove(&mut self, goal: &'a Goal, cell: usize) {
+        self.cells[cell] = None;
+        self.used.remove(goal.name.trim());
+
+        for category in &goal.categories {
+            if let Some(count) = self.board_counts.get_mut(category.as_str()) {
+                *count -= 1;
+            }
+        }
+
+        for &line_idx in &self.cell_lines[cell] {
+            for category in &goal.categories {
+                if let Some(count) = self.line_counts[line_idx].get_mut(category.as_str()) {
+                    *count -= 1;
+                }
+            }
+            self.line_totals[line_idx] -= goal.difficulty;
+            self.line_filled[line_idx] -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use bridge::board::DifficultyTarget;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    fn pool(count: usize, categories: &[&str]) -> GoalPool {
+        GoalPool {
+            goals: (0..count)
+                .map(|i| Goal {
+                    name: format!("Goal {i}"),
+                    difficulty: 1,
+                    categories: categories.iter().map(|c| c.to_string()).collect(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn solve_3x3(
+        pool: &GoalPool,
+        constraints: &BoardConstraints,
+    ) -> Result<Vec<Goal>, SolveFailure> {
+        solve(
+            pool,
+            constraints,
+            GridSize::Size3,
+            &mut ChaCha8Rng::seed_from_u64(0),
+        )
+    }
+
+    #[test]
+    fn solves_feasible_board() {
+        let pool = pool(12, &["a"]);
+        let constraints = BoardConstraints {
+            board_max: HashMap::from([("a".to_string(), 9)]),
+            ..Default::default()
+        };
+
+        assert_eq!(solve_3x3(&pool, &constraints).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn reports_small_pool() {
+        let failure = solve_3x3(&pool(8, &[]), &BoardConstraints::default()).unwrap_err();
+
+        assert_eq!(failure.constraint, Constraint::PoolSize);
+        assert_eq!(failure.filled, 8);
+        assert!(!failure.pinned);
+    }
+
+    #[test]
+    fn counts_pinned_pool_goals_once() {
+        let pool = pool(8, &["a"]);
+        let mut constraints = BoardConstraints {
+            line_max: HashMap::from([("a".to_string(), 1)]),
+            pinned: BTreeMap::from([(0, pool.goals[0].clone()), (5, pool.goals[1].clone())]),
+            ..Default::default()
+        };
+        let failure = solve_3x3(&pool, &constraints).unwrap_err();
+        assert_eq!(failure.constraint, Constraint::PoolSize);
+
+        constraints.line_max.clear();
+        constraints.pinned.insert(
+            1,
+            Goal {
+                name: "Pinned".to_string(),
+                ..Default::default()
+            },
+        );
+        assert_eq!(solve_3x3(&pool, &constraints).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn reports_blocking_board_limit() {
+        let constraints = BoardConstraints {
+            board_max: HashMap::from([("a".to_string(), 5)]),
+            ..Default::default()
+        };
+        let failure = solve_3x3(&pool(15, &["a"]), &constraints).unwrap_err();
+
+        assert_eq!(failure.constraint, Constraint::BoardMax("a".to_string()));
+        assert_eq!(failure.filled, 5);
+        assert!(failure.gave_up);
+        assert_eq!(
+            failure.to_string(),
+            "Gave up searching, mostly blocked by the board limit of 'a', best attempt filled 5 of 9 cells"
+        );
+    }
+
+    #[test]
+    fn reports_unreachable_line_difficulty() {
+        let constraints = BoardConstraints {
+            line_difficulty: Some(DifficultyTarget {
+                target: 10,
+                tolerance: 0,
+            }),
+            ..Default::default()
+        };
+        let failure = solve_3x3(&pool(15, &[]), &constraints).unwrap_err();
+
+        assert_eq!(failure.constraint, Constraint::LineDifficulty);
+        assert_eq!(failure.filled, 0);
+    }
+
+    #[test]
+    fn reports_pinned_cells_breaking_rules() {
+        let pool = pool(15, &["a"]);
+        let constraints = BoardConstraints {
+            line_max: HashMap::from([("a".to_string(), 1)]),
+            pinned: BTreeMap::from([(0, pool.goals[0].clone()), (1, pool.goals[1].clone())]),
+            ..Default::default()
+        };
+        let failure = solve_3x3(&pool, &constraints).unwrap_err();
+
+        assert_eq!(failure.constraint, Constraint::LineMax("a".to_string()));
+        assert!(failure.pinned);
+        assert_eq!(
+            failure.to_string(),
+            "Pinned cells already break the line limit of 'a'"
+        );
+    }
+}
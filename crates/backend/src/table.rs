@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use bridge::{
+    board::GridSize,
+    goal::{GoalFilter, GoalPool},
+    table::{self, ColumnMapping, Delimiter},
+};
+use chrono::Local;
+
+use crate::backend::BackendState;
+
+impl BackendState {
+    pub async fn import_pool_table(
+        &self,
+        path: &Path,
+        mapping: &ColumnMapping,
+    ) -> Result<GoalPool> {
+        read_pool_table(path, mapping)
+    }
+
+    pub async fn import_board_table(&self, path: &Path) -> Result<(GridSize, Vec<String>)> {
+        let rows = read_table(path)?;
+
+        Ok(table::grid_from_rows(&rows)?)
+    }
+
+    /// Writes the filtered pool one goal per row, returns the file name.
+    pub async fn export_pool_table(
+        &self,
+        pool: GoalPool,
+        filter: GoalFilter,
+        delimiter: Delimiter,
+    ) -> Result<String> {
+        let date = Local::now();
+        let filename = format!(
+            "{}_pool.{}",
+            date.format("%Y-%m-%d_%H-%M-%S"),
+            delimiter.extension()
+        );
+        let rows = table::pool_rows(&pool.filtered(&filter));
+        std::fs::write(
+            self.export_dir.join(&filename),
+            table::write(&rows, delimiter),
+        )?;
+
+        Ok(filename)
+    }
+
+    /// Writes the board as a grid of cells, returns the file name.
+    pub async fn export_board_table(
+        &self,
+        grid_size: GridSize,
+        cells: Vec<String>,
+        delimiter: Delimiter,
+    ) -> Result<String> {
+        let date = Local::now();
+        let filename = format!(
+            "{}_board.{}",
+            date.format("%Y-%m-%d_%H-%M-%S"),
+            delimiter.extension()
+        );
+        let rows = table::grid_rows(grid_size, &cells);
+        std::fs::write(
+            self.export_dir.join(&filename),
+            table::write(&rows, delimiter),
+        )?;
+
+        Ok(filename)
+    }
+}
+
+/// Goal pool from a CSV or TSV file, named after the file.
+pub fn read_pool_table(path: &Path, mapping: &ColumnMapping) -> Result<GoalPool> {
+    let rows = read_table(path)?;
+    let mut pool = table::pool_from_rows(&rows, mapping)?;
+    pool.game_name = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    Ok(pool)
+}
+
+/// Rows of a CSV file, or a TSV file for `.tsv` and `.tab` extensions.
+fn read_table(path: &Path) -> Result<Vec<Vec<String>>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read '{}'", path.display()))?;
+
+    Ok(table::parse(&text, Delimiter::from_path(path))?)
+}
@@ -0,0 +1,58 @@
+//! Text measuring and wrapping for the PDF and image exporters. Widths and heights are in the
+//! same unit as the font size.
+
+/// Embedded so exports render the same on every machine, without any system fonts.
+pub const FONT: &[u8] = include_bytes!("../fonts/DejaVuSans.ttf");
+pub const FONT_FAMILY: &str = "DejaVu Sans";
+
+pub const LINE_HEIGHT: f32 = 1.2;
+/// Average glyph width of the embedded font, in em.
+const AVG_CHAR_WIDTH: f32 = 0.55;
+const SIZE_STEP: f32 = 0.5;
+
+pub fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * AVG_CHAR_WIDTH
+}
+
+/// Largest font size from `max` down to `min` that fits the text on one line.
+pub fn fit_line(text: &str, width: f32, max: f32, min: f32) -> f32 {
+    let mut size = max;
+    while text_width(text, size) > width && size > min {
+        size -= SIZE_STEP;
+    }
+
+    size
+}
+
+/// Largest font size from `max` down to `min` the wrapped text fits the box with, along with its
+/// lines.
+pub fn fit_text(text: &str, width: f32, height: f32, max: f32, min: f32) -> (f32, Vec<String>) {
+    let mut size = max;
+
+    loop {
+        let lines = wrap(text, width, size);
+        let fits = lines.len() as f32 * size * LINE_HEIGHT <= height
+            && lines.iter().all(|l| text_width(l, size) <= width);
+        if fits || size <= min {
+            return (size, lines);
+        }
+
+        size -= SIZE_STEP;
+    }
+}
+
+fn wrap(text: &str, width: f32, size: f32) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if text_width(&format!("{} {}", line, word), size) <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_owned()),
+        }
+    }
+
+    lines
+}
@@ -0,0 +1,381 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use anyhow::{Result, bail};
+use bridge::{
+    board::{GenerateOptions, GeneratedBoard},
+    goal::{Goal, GoalPool},
+    tournament::{BoardReport, FairnessReport, PlanOptions},
+};
+use chrono::Local;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    backend::BackendState,
+    bulk::{self, Progress},
+    generator,
+};
+
+/// Boards generated for every slot, the one closest to the target difficulty is kept.
+const PLAN_ATTEMPTS: usize = 12;
+/// Goals offered to each board per cell, leaves the generator room to balance and follow rules.
+const CANDIDATES_PER_CELL: usize = 2;
+/// Difficulty points an attempt may be further off target to use one less repeated goal.
+const REPEAT_PENALTY: f64 = 1.0;
+/// Extra penalty for a goal that was on a board the round before.
+const RECENT_PENALTY: f64 = 2.0;
+
+impl BackendState {
+    /// Plans and writes a tournament set into a new folder with an `index.json` and a fairness
+    /// report, returns the folder name and the report.
+    pub async fn plan_tournament(
+        &self,
+        pool: GoalPool,
+        options: PlanOptions,
+    ) -> Result<(String, FairnessReport)> {
+        let state = self.clone();
+        tokio::task::spawn_blocking(move || state.write_tournament(pool, options)).await?
+    }
+
+    fn write_tournament(
+        &self,
+        pool: GoalPool,
+        options: PlanOptions,
+    ) -> Result<(String, FairnessReport)> {
+        let date = Local::now();
+        let folder = format!("{}_tournament", date.format("%Y-%m-%d_%H-%M-%S"));
+        let dir = self.export_dir.join(&folder);
+
+        let mut progress =
+            Progress::new(self.send.clone(), options.rounds * options.boards_per_round);
+        let boards = plan(&pool, &options, |done| progress.update(done))?;
+
+        std::fs::create_dir_all(&dir)?;
+        let mut reports = Vec::with_capacity(boards.len());
+        let mut seen: HashSet<&str> = HashSet::new();
+        for (idx, board) in boards.iter().enumerate() {
+            let (round, slot) = (
+                idx / options.boards_per_round,
+                idx % options.boards_per_round,
+            );
+            let goals: Vec<String> = board
+                .goals
+                .iter()
+                .map(|g| g.name.trim().to_owned())
+                .collect();
+            let file = format!("round_{:02}_board_{:02}.json", round + 1, slot + 1);
+            bulk::write_board(
+                &dir.join(&file),
+                options.format,
+                &pool.game_name,
+                board.grid_size,
+                &goals,
+            )?;
+
+            reports.push(BoardReport {
+                round: round + 1,
+                board: slot + 1,
+                file,
+                seed: board.seed,
+                total_difficulty: board.goals.iter().map(|g| g.difficulty).sum(),
+                line_difficulty_min: board.line_spread.min,
+                line_difficulty_max: board.line_spread.max,
+                repeated_goals: board
+                    .goals
+                    .iter()
+                    .filter(|g| seen.contains(g.pool_name()))
+                    .count(),
+            });
+            if slot + 1 == options.boards_per_round {
+                seen.extend(
+                    boards[idx + 1 - options.boards_per_round..=idx]
+                        .iter()
+                        .flat_map(|b| b.goals.iter().map(|g| g.pool_name())),
+                );
+            }
+        }
+
+        self.record_usage(
+            reports
+                .iter()
+                .zip(&boards)
+                .map(|(report, board)| {
+                    (
+                        format!("{}/{}", folder, report.file),
+                        board.goals.iter().map(|g| g.name.clone()).collect(),
+                    )
+                })
+                .collect(),
+        )?;
+
+        let report = fairness_report(&boards, options.boards_per_round, reports);
+        std::fs::write(
+            dir.join("index.json"),
+            serde_json::to_string_pretty(&report)?,
+        )?;
+        std::fs::write(dir.join("report.md"), report_markdown(&report))?;
+
+        Ok((folder, report))
+    }
+}
+
+/// Generates `rounds * boards_per_round` boards, round by round. No goal repeats within a round,
+/// the least used goals are offered first so appearances spread out over the rounds, and of
+/// several attempts per board the one closest to the pool's average total difficulty is kept.
+pub fn plan(
+    pool: &GoalPool,
+    options: &PlanOptions,
+    mut progress: impl FnMut(usize),
+) -> Result<Vec<GeneratedBoard>> {
+    let grid_size = options.generate.grid_size;
+    let cells = grid_size.cell_count();
+
+    if options.rounds == 0 || options.boards_per_round == 0 {
+        bail!("A tournament needs at least one round with one board");
+    }
+
+    let goals: Vec<&Goal> = pool
+        .goals
+        .iter()
+        .filter(|g| !g.name.trim().is_empty())
+        .collect();
+    if goals.len() < cells * options.boards_per_round {
+        bail!(
+            "{} boards per round without repeats need {} goals, pool has {}",
+            options.boards_per_round,
+            cells * options.boards_per_round,
+            goals.len()
+        );
+    }
+
+    let mean_difficulty =
+        goals.iter().map(|g| g.difficulty).sum::<usize>() as f64 / goals.len() as f64;
+    let target = mean_difficulty * cells as f64;
+
+    let seed = options.seed.unwrap_or_else(rand::random);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let mut appearances: HashMap<&str, usize> = HashMap::new();
+    let mut last_round: HashSet<&str> = HashSet::new();
+    let mut boards = Vec::with_capacity(options.rounds * options.boards_per_round);
+
+    for round in 0..options.rounds {
+        let mut this_round: HashSet<&str> = HashSet::new();
+
+        for slot in 0..options.boards_per_round {
+            let mut available: Vec<&Goal> = goals
+                .iter()
+                .copied()
+                .filter(|g| !this_round.contains(g.name.trim()))
+                .collect();
+            let key = |g: &Goal| {
+                (
+                    appearances.get(g.pool_name()).copied().unwrap_or(0),
+                    last_round.contains(g.pool_name()),
+                )
+            };
+            available.sort_by_key(|g| key(g));
+
+            // Keep every goal tied with the last one offered, so the cut doesn't favour pool order.
+            let wanted = (cells * CANDIDATES_PER_CELL).min(available.len());
+            let cut = key(available[wanted - 1]);
+            let offered = available.iter().take_while(|g| key(g) <= cut).count();
+            let sub_pool = GoalPool {
+                goals: available[..offered].iter().map(|g| (*g).clone()).collect(),
+                ..pool.clone()
+            };
+
+            let mut best: Option<(f64, GeneratedBoard)> = None;
+            let mut last_error = None;
+            for _ in 0..PLAN_ATTEMPTS {
+                let generate_options = GenerateOptions {
+                    seed: Some(rng.random()),
+                    pinned: Default::default(),
+                    ..options.generate.clone()
+                };
+                match generator::generate(&sub_pool, &generate_options) {
+                    Ok(board) => {
+                        let total: usize = board.goals.iter().map(|g| g.difficulty).sum();
+                        let repeats: f64 = board
+                            .goals
+                            .iter()
+                            .map(|g| {
+                                let (count, recent) = key(g);
+                                count as f64 * REPEAT_PENALTY
+                                    + if recent { RECENT_PENALTY } else { 0.0 }
+                            })
+                            .sum();
+                        let cost = (total as f64 - target).abs() + repeats;
+                        if best.as_ref().is_none_or(|(c, _)| cost < *c) {
+                            best = Some((cost, board));
+                        }
+                    }
+                    Err(e) => last_error = Some(e),
+                }
+            }
+
+            let Some((_, board)) = best else {
+                let e = last_error.unwrap();
+                bail!("Round {} board {}: {}", round + 1, slot + 1, e);
+            };
+
+            for goal in &board.goals {
+                let name = goal.pool_name();
+                let Some(name) = goals.iter().find(|g| g.name.trim() == name) else {
+                    continue;
+                };
+                this_round.insert(name.name.trim());
+                *appearances.entry(name.name.trim()).or_default() += 1;
+            }
+
+            boards.push(board);
+            progress(boards.len());
+        }
+
+        last_round = this_round;
+    }
+
+    Ok(boards)
+}
+
+fn fairness_report(
+    boards: &[GeneratedBoard],
+    boards_per_round: usize,
+    reports: Vec<BoardReport>,
+) -> FairnessReport {
+    let totals: Vec<usize> = reports.iter().map(|r| r.total_difficulty).collect();
+    let mean = totals.iter().sum::<usize>() as f64 / totals.len().max(1) as f64;
+    let variance = totals
+        .iter()
+        .map(|&t| (t as f64 - mean).powi(2))
+        .sum::<f64>()
+        / totals.len().max(1) as f64;
+
+    let mut rounds_by_goal: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, board) in boards.iter().enumerate() {
+        for goal in &board.goals {
+            rounds_by_goal
+                .entry(goal.pool_name())
+                .or_default()
+                .push(idx / boards_per_round);
+        }
+    }
+
+    FairnessReport {
+        difficulty_min: totals.iter().copied().min().unwrap_or_default(),
+        difficulty_max: totals.iter().copied().max().unwrap_or_default(),
+        difficulty_mean: mean,
+        difficulty_std_dev: variance.sqrt(),
+        distinct_goals: rounds_by_goal.len(),
+        max_appearances: rounds_by_goal.values().map(Vec::len).max().unwrap_or(0),
+        min_round_gap: rounds_by_goal
+            .values()
+            .flat_map(|rounds| rounds.windows(2).map(|w| w[1] - w[0]))
+            .min(),
+        boards: reports,
+    }
+}
+
+fn report_markdown(report: &FairnessReport) -> String {
+    let mut md = String::new();
+
+    _ = writeln!(md, "# Tournament Fairness Report\n");
+    _ = writeln!(
+        md,
+        "- Board difficulty: {}..{}, mean {:.1}, standard deviation {:.2}",
+        report.difficulty_min,
+        report.difficulty_max,
+        report.difficulty_mean,
+        report.difficulty_std_dev
+    );
+    _ = writeln!(md, "- Distinct goals: {}", report.distinct_goals);
+    _ = writeln!(
+        md,
+        "- Most appearances of one goal: {}",
+        report.max_appearances
+    );
+    match report.min_round_gap {
+        Some(gap) => _ = writeln!(md, "- Fewest rounds between repeats: {}", gap),
+        None => _ = writeln!(md, "- No goal appears twice"),
+    }
+
+    _ = writeln!(
+        md,
+        "\n| Round | Board | File | Seed | Difficulty | Lines | Repeated goals |"
+    );
+    _ = writeln!(md, "|---|---|---|---|---|---|---|");
+    for board in &report.boards {
+        _ = writeln!(
+            md,
+            "| {} | {} | {} | {} | {} | {}..{} | {} |",
+            board.round,
+            board.board,
+            board.file,
+            board.seed,
+            board.total_difficulty,
+            board.line_difficulty_min,
+            board.line_difficulty_max,
+            board.repeated_goals
+        );
+    }
+
+    md
+}
+
+#[cfg(test)]
+mod tests {
+    use bridge::board::GridSize;
+
+    use super::*;
+
+    #[test]
+    fn plan_spreads_goals_and_balances_difficulty() {
+        let pool = GoalPool {
+            goals: (0..36)
+                .map(|i| Goal {
+                    name: format!("Goal {i}"),
+                    difficulty: i % 4 + 1,
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let options = PlanOptions {
+            rounds: 4,
+            boards_per_round: 2,
+            seed: Some(0),
+            generate: GenerateOptions {
+                grid_size: GridSize::Size3,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut done = 0;
+        let boards = plan(&pool, &options, |d| done = d).unwrap();
+        assert_eq!(boards.len(), 8);
+        assert_eq!(done, 8);
+
+        for round in boards.chunks(2) {
+            let names: HashSet<&str> = round
+                .iter()
+                .flat_map(|b| b.goals.iter().map(|g| g.name.as_str()))
+                .collect();
+            assert_eq!(names.len(), 18);
+        }
+
+        // Least used goals are offered first, so all of them get used and none piles up.
+        let report = fairness_report(&boards, 2, vec![]);
+        assert_eq!(report.distinct_goals, 36);
+        assert!(report.max_appearances <= 3);
+
+        // Pool average is 2.5 per cell, so 22.5 per board.
+        for board in &boards {
+            let total: usize = board.goals.iter().map(|g| g.difficulty).sum();
+            assert!((21..=24).contains(&total), "board total {total}");
+        }
+    }
+}
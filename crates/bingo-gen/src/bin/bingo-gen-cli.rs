@@ -0,0 +1,21 @@
+//! Command line tools as a console app. The main binary is a GUI app on Windows, so the console
+//! it's started from doesn't wait for it or show what it prints.
+
+use std::process::ExitCode;
+
+use bingo_gen::cli;
+
+fn main() -> ExitCode {
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .init();
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::run(&args) {
+        Some(result) => cli::exit_code(result),
+        None => {
+            eprintln!("{}", cli::USAGE);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{Context, Result, bail};
+use bridge::{
+    board::{GenerateMode, GenerateOptions, GridSize},
+    code::{self, BoardCode},
+    goal::GoalPool,
+    image::{BoardImage, ImageCell, ImageFormat},
+    project::PROJECT_EXTENSION,
+};
+
+pub const USAGE: &str = "Usage:
+  bingo-gen [<board.bingo>]
+  bingo-gen code encode --pool <pool.json> [--seed <seed>] [--size <3-9>] [--mode random|balanced|constrained]
+  bingo-gen code decode <code> [--pool <pool.json>]
+  bingo-gen verify <export.json>
+  bingo-gen image [<code>] --pool <pool.json> --out <board.png|board.svg> [--seed <seed>] [--size <3-9>] [--mode random|balanced|constrained] [--title <title>] [--colors]
+  bingo-gen html [<code>] --pool <pool.json> --out <board.html> [--seed <seed>] [--size <3-9>] [--mode random|balanced|constrained] [--title <title>] [--spoiler]
+
+On Windows bingo-gen has no console, run the commands with bingo-gen-cli instead.";
+
+/// Runs a command line command, returns `None` when there's none and the app should start.
+pub fn run(args: &[String]) -> Option<Result<()>> {
+    match args.first().map(String::as_str) {
+        None => None,
+        Some(_) if project_path(args).is_some() => None,
+        Some("code") => Some(code(&args[1..])),
+        Some("verify") => Some(verify(&args[1..])),
+        Some("image") => Some(image(&args[1..])),
+        Some("html") => Some(html(&args[1..])),
+        Some("help" | "--help" | "-h") => {
+            println!("{USAGE}");
+            Some(Ok(()))
+        }
+        Some(command) => Some(Err(anyhow::anyhow!("Unknown command '{command}'\n{USAGE}"))),
+    }
+}
+
+/// Prints the error of a finished command.
+pub fn exit_code(result: Result<()>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Project file the app was started with, opened once it's up.
+pub fn project_path(args: &[String]) -> Option<PathBuf> {
+    let [arg] = args else {
+        return None;
+    };
+
+    Path::new(arg)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(PROJECT_EXTENSION))
+        .then(|| PathBuf::from(arg))
+}
+
+fn code(args: &[String]) -> Result<()> {
+    let (command, args) = args.split_first().context(USAGE)?;
+    let options = Options::parse(args)?;
+
+    match command.as_str() {
+        "encode" => {
+            let pool = options.pool()?.context("Encoding needs a goal pool")?;
+            let board = backend::generator::generate(
+                &pool,
+                &GenerateOptions {
+                    grid_size: options.grid_size,
+                    seed: options.seed,
+                    mode: options.mode,
+                    ..Default::default()
+                },
+            )?;
+
+            print_board(
+                board.grid_size,
+                &board
+                    .goals
+                    .iter()
+                    .map(|g| g.name.clone())
+                    .collect::<Vec<_>>(),
+            );
+            println!("{}", BoardCode::generated(&pool, &board).encode());
+        }
+        "decode" => {
+            let text = options.positional.first().context(USAGE)?;
+            let board_code = BoardCode::decode(text)?;
+
+            match &board_code {
+                BoardCode::Generated { pool_hash, .. } => {
+                    let pool = options
+                        .pool()?
+                        .context("This board was generated, decoding it needs its goal pool")?;
+                    if code::pool_hash(&pool) != *pool_hash {
+                        bail!("This board was generated from a different goal pool");
+                    }
+
+                    let board =
+                        backend::generator::generate(&pool, &board_code.options().unwrap())?;
+                    println!("Seed {}, {:?} mode", board.seed, board.mode);
+                    print_board(
+                        board.grid_size,
+                        &board
+                            .goals
+                            .iter()
+                            .map(|g| g.name.clone())
+                            .collect::<Vec<_>>(),
+                    );
+                }
+                BoardCode::Custom { grid_size, cells } => print_board(*grid_size, cells),
+            }
+        }
+        _ => bail!("Unknown code command '{command}'\n{USAGE}"),
+    }
+
+    Ok(())
+}
+
+fn verify(args: &[String]) -> Result<()> {
+    let path = args.first().context(USAGE)?;
+    let report = backend::metadata::verify_export(&PathBuf::from(path))?;
+
+    if let Some(version) = &report.other_version {
+        println!("Exported with version {version}");
+    }
+    if report.differences.is_empty() {
+        println!("'{}' matches the regenerated board", report.export_file);
+        return Ok(());
+    }
+
+    for difference in &report.differences {
+        println!("{difference}");
+    }
+    bail!(
+        "'{}' differs from the regenerated board in {} places",
+        report.export_file,
+        report.differences.len()
+    );
+}
+
+fn image(args: &[String]) -> Result<()> {
+    let options = Options::parse(args)?;
+    let out = options.out.as_ref().context(USAGE)?;
+    let format = match out.extension().and_then(|e| e.to_str()) {
+        Some("png") => ImageFormat::Png,
+        Some("svg") => ImageFormat::Svg,
+        _ => bail!("Image has to be a .png or .svg file"),
+    };
+
+    let mut image = board_image(&options)?;
+    if !options.colors {
+        image.cells.iter_mut().for_each(|cell| cell.category = None);
+    }
+    backend::image::write_image(out, &image, format)?;
+    println!("Wrote '{}'", out.display());
+
+    Ok(())
+}
+
+fn html(args: &[String]) -> Result<()> {
+    let options = Options::parse(args)?;
+    let out = options.out.as_ref().context(USAGE)?;
+
+    backend::html::write_html(out, &board_image(&options)?, options.spoiler)?;
+    println!("Wrote '{}'", out.display());
+
+    Ok(())
+}
+
+/// Board of the code argument, or a freshly generated one, with the first category of each goal.
+fn board_image(options: &Options) -> Result<BoardImage> {
+    let pool = options.pool()?;
+    let board_code = options
+        .positional
+        .first()
+        .map(|text| BoardCode::decode(text))
+        .transpose()?;
+
+    let (grid_size, cells): (GridSize, Vec<ImageCell>) = match board_code {
+        Some(BoardCode::Custom { grid_size, cells }) => (
+            grid_size,
+            cells
+                .into_iter()
+                .map(|text| {
+                    let category = pool
+                        .as_ref()
+                        .and_then(|p| p.resolve(&text))
+                        .and_then(|g| g.categories.first().cloned());
+                    ImageCell { text, category }
+                })
+                .collect(),
+        ),
+        board_code => {
+            let pool = pool.context("Drawing a generated board needs its goal pool")?;
+            let generate_options = match &board_code {
+                Some(board_code) => {
+                    if let BoardCode::Generated { pool_hash, .. } = board_code
+                        && code::pool_hash(&pool) != *pool_hash
+                    {
+                        bail!("This board was generated from a different goal pool");
+                    }
+                    board_code.options().unwrap()
+                }
+                None => GenerateOptions {
+                    grid_size: options.grid_size,
+                    seed: options.seed,
+                    mode: options.mode,
+                    ..Default::default()
+                },
+            };
+
+            let board = backend::generator::generate(&pool, &generate_options)?;
+            (
+                board.grid_size,
+                board
+                    .goals
+                    .into_iter()
+                    .map(|g| ImageCell {
+                        category: g.categories.first().cloned(),
+                        text: g.name,
+                    })
+                    .collect(),
+            )
+        }
+    };
+
+    Ok(BoardImage {
+        title: options.title.clone(),
+        grid_size,
+        cells,
+    })
+}
+
+struct Options {
+    pool: Option<PathBuf>,
+    seed: Option<u64>,
+    grid_size: GridSize,
+    mode: GenerateMode,
+    out: Option<PathBuf>,
+    title: String,
+    colors: bool,
+    spoiler: bool,
+    positional: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut options = Options {
+            pool: None,
+            seed: None,
+            grid_size: GridSize::default(),
+            mode: GenerateMode::default(),
+            out: None,
+            title: String::new(),
+            colors: false,
+            spoiler: false,
+            positional: Vec::new(),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .with_context(|| format!("'{arg}' needs a value"))
+            };
+
+            match arg.as_str() {
+                "--pool" => options.pool = Some(PathBuf::from(value()?)),
+                "--seed" => options.seed = Some(value()?.parse().context("Invalid seed")?),
+                "--size" => {
+                    options.grid_size = value()?
+                        .parse()
+                        .ok()
+                        .and_then(GridSize::from_repr)
+                        .context("Grid size has to be between 3 and 9")?
+                }
+                "--mode" => {
+                    options.mode = match value()?.to_lowercase().as_str() {
+                        "random" => GenerateMode::Random,
+                        "balanced" => GenerateMode::Balanced,
+                        "constrained" => GenerateMode::Constrained,
+                        mode => bail!("Unknown mode '{mode}'"),
+                    }
+                }
+                "--out" => options.out = Some(PathBuf::from(value()?)),
+                "--title" => options.title = value()?.clone(),
+                "--colors" => options.colors = true,
+                "--spoiler" => options.spoiler = true,
+                _ => options.positional.push(arg.clone()),
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn pool(&self) -> Result<Option<GoalPool>> {
+        self.pool
+            .as_deref()
+            .map(backend::pool::read_goal_pool)
+            .transpose()
+    }
+}
+
+fn print_board(grid_size: GridSize, cells: &[String]) {
+    for row in cells.chunks(grid_size.side()) {
+        println!(
+            "{}",
+            row.iter().map(|c| c.trim()).collect::<Vec<_>>().join(" | ")
+        );
+    }
+}
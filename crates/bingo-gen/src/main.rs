@@ -1,12 +1,20 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::fs;
+use std::{fs, process::ExitCode};
 
-fn main() {
+use bingo_gen::cli;
+use bridge::message::MessageToBackend;
+
+fn main() -> ExitCode {
     tracing_subscriber::fmt()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
         .init();
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(result) = cli::run(&args) {
+        return cli::exit_code(result);
+    }
+
     let exe_folder = std::env::current_exe().unwrap();
     let export_path = exe_folder.parent().unwrap().join("export");
 
@@ -21,5 +29,10 @@ fn main() {
         backend_handle.clone(),
         backend_receiver,
     );
+    if let Some(path) = cli::project_path(&args) {
+        backend_handle.send(MessageToBackend::OpenProject { path });
+    }
     frontend::start("Bingo Gen", backend_handle, frontend_receiver);
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,214 @@
+use std::collections::{BTreeMap, HashMap};
+
+use strum::{EnumIter, FromRepr};
+
+use crate::{
+    card::ExportFormat,
+    goal::{Goal, GoalPool},
+    history::AvoidRecent,
+};
+
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    PartialOrd,
+    Ord,
+    EnumIter,
+    FromRepr,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[repr(usize)]
+pub enum GridSize {
+    Size3 = 3,
+    Size4,
+    #[default]
+    Size5,
+    Size6,
+    Size7,
+    Size8,
+    Size9,
+}
+
+impl GridSize {
+    pub fn side(self) -> usize {
+        self as usize
+    }
+
+    pub fn cell_count(self) -> usize {
+        self.side() * self.side()
+    }
+
+    /// Cell indices of every row, column and both diagonals, row-major.
+    pub fn lines(self) -> Vec<Vec<usize>> {
+        let n = self.side();
+        let mut lines = Vec::with_capacity(n * 2 + 2);
+
+        lines.extend((0..n).map(|y| (0..n).map(|x| y * n + x).collect()));
+        lines.extend((0..n).map(|x| (0..n).map(|y| y * n + x).collect()));
+        lines.push((0..n).map(|i| i * n + i).collect());
+        lines.push((0..n).map(|i| i * n + (n - 1 - i)).collect());
+
+        lines
+    }
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, Eq, PartialEq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum GenerateMode {
+    #[default]
+    Random,
+    /// Even total difficulty over all lines.
+    Balanced,
+    /// Backtracking fill that satisfies every [`BoardConstraints`] rule.
+    Constrained,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    pub grid_size: GridSize,
+    /// Random seed is picked by the backend when empty.
+    pub seed: Option<u64>,
+    pub mode: GenerateMode,
+    pub line_difficulty: Option<DifficultyTarget>,
+    /// Goals kept in place, by row-major board index.
+    pub pinned: BTreeMap<usize, Goal>,
+    pub avoid_recent: Option<AvoidRecent>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BulkOptions {
+    pub count: usize,
+    /// Boards use the seeds `first_seed..first_seed + count`, random when empty.
+    pub first_seed: Option<u64>,
+    /// Seed and pinned goals are ignored.
+    pub generate: GenerateOptions,
+    pub format: ExportFormat,
+    /// No goal shows up on more than one board of the set.
+    pub unique_goals: bool,
+}
+
+/// Total difficulty every line has to land in, `target - tolerance..=target + tolerance`.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub struct DifficultyTarget {
+    pub target: usize,
+    pub tolerance: usize,
+}
+
+impl DifficultyTarget {
+    pub fn min(&self) -> usize {
+        self.target.saturating_sub(self.tolerance)
+    }
+
+    pub fn max(&self) -> usize {
+        self.target + self.tolerance
+    }
+}
+
+/// Everything the constrained generator has to satisfy at once, on top of the pool exclusion and
+/// line rules.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BoardConstraints {
+    pub board_min: HashMap<String, usize>,
+    pub board_max: HashMap<String, usize>,
+    pub line_min: HashMap<String, usize>,
+    pub line_max: HashMap<String, usize>,
+    pub line_difficulty: Option<DifficultyTarget>,
+    /// Goals that stay where they are, by row-major board index.
+    pub pinned: BTreeMap<usize, Goal>,
+}
+
+impl BoardConstraints {
+    pub fn from_pool(pool: &GoalPool) -> Self {
+        Self {
+            board_min: pool.board_minimums.clone(),
+            board_max: pool.board_limits.clone(),
+            line_min: pool.line_minimums.clone(),
+            line_max: pool.line_limits.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GeneratedBoard {
+    pub grid_size: GridSize,
+    pub seed: u64,
+    /// Row-major, `grid_size.cell_count()` long.
+    pub goals: Vec<Goal>,
+    pub line_spread: LineSpread,
+    pub mode: GenerateMode,
+    pub line_difficulty: Option<DifficultyTarget>,
+    /// Goals pinned when generating, the seed alone doesn't reproduce the board with them.
+    pub pinned: BTreeMap<usize, Goal>,
+    /// Some cells were rerolled after generating, the seed alone no longer reproduces the board.
+    pub rerolled: bool,
+    /// Recently used goals were avoided, the board depends on the usage history at the time.
+    pub avoided_recent: bool,
+}
+
+impl GeneratedBoard {
+    pub fn new(grid_size: GridSize, seed: u64, goals: Vec<Goal>) -> Self {
+        let difficulties: Vec<usize> = goals.iter().map(|g| g.difficulty).collect();
+
+        Self {
+            grid_size,
+            seed,
+            line_spread: LineSpread::of(grid_size, &difficulties),
+            goals,
+            mode: GenerateMode::default(),
+            line_difficulty: None,
+            pinned: BTreeMap::new(),
+            rerolled: false,
+            avoided_recent: false,
+        }
+    }
+
+    /// Whether the seed and options alone regenerate this board.
+    pub fn reproducible(&self) -> bool {
+        self.pinned.is_empty() && !self.rerolled && !self.avoided_recent
+    }
+
+    pub fn options(&self) -> GenerateOptions {
+        GenerateOptions {
+            grid_size: self.grid_size,
+            seed: Some(self.seed),
+            mode: self.mode,
+            line_difficulty: self.line_difficulty,
+            pinned: self.pinned.clone(),
+            avoid_recent: None,
+        }
+    }
+}
+
+/// Lowest and highest total difficulty over all rows, columns and diagonals.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineSpread {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl LineSpread {
+    pub fn of(grid_size: GridSize, difficulties: &[usize]) -> Self {
+        let totals: Vec<usize> = grid_size
+            .lines()
+            .iter()
+            .map(|line| line.iter().map(|&idx| difficulties[idx]).sum())
+            .collect();
+
+        Self {
+            min: totals.iter().copied().min().unwrap_or_default(),
+            max: totals.iter().copied().max().unwrap_or_default(),
+        }
+    }
+
+    pub fn spread(&self) -> usize {
+        self.max - self.min
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use crate::{board::GridSize, print::PaperSize};
+
+/// Text of the free center space on printed cards.
+pub const FREE_SPACE: &str = "FREE";
+
+/// Every printed card of a bingo night, saved next to the PDF.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CardSet {
+    pub generator_version: String,
+    pub created_at: String,
+    pub pdf_file: String,
+    pub paper: PaperSize,
+    pub title: String,
+    pub game_name: String,
+    pub grid_size: GridSize,
+    pub free_center: bool,
+    /// Goal texts of every card, row-major, card 1 first.
+    pub cards: Vec<Vec<String>>,
+}
+
+impl CardSet {
+    /// Every goal on any card, in order of first appearance. The free space isn't a goal.
+    pub fn goals(&self) -> Vec<String> {
+        let mut seen = HashSet::new();
+
+        self.cards
+            .iter()
+            .flatten()
+            .filter(|g| !self.is_free(g))
+            .filter(|g| seen.insert(g.as_str()))
+            .cloned()
+            .collect()
+    }
+
+    fn is_free(&self, goal: &str) -> bool {
+        self.free_center && goal == FREE_SPACE
+    }
+
+    /// Lines and blackout of card `number`, counting from 1, `None` if there's no such card.
+    pub fn check(&self, number: usize, game: &CallerGame) -> Option<CardStatus> {
+        let card = self.cards.get(number.checked_sub(1)?)?;
+        let called: HashSet<&str> = game.called().iter().map(String::as_str).collect();
+        let marked: Vec<bool> = card
+            .iter()
+            .map(|g| self.is_free(g) || called.contains(g.as_str()))
+            .collect();
+
+        let lines = self
+            .grid_size
+            .lines()
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.iter().all(|&idx| marked[idx]))
+            .map(|(idx, _)| line_name(self.grid_size, idx))
+            .collect();
+
+        Some(CardStatus {
+            marked: marked.iter().filter(|m| **m).count(),
+            lines,
+            blackout: marked.iter().all(|m| *m),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CardStatus {
+    /// Cells called so far, the free space included.
+    pub marked: usize,
+    /// Names of the complete lines, like "Row 2".
+    pub lines: Vec<String>,
+    pub blackout: bool,
+}
+
+/// Call order of a game, saved after every call so it can be resumed.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CallerGame {
+    pub card_set_file: String,
+    pub seed: u64,
+    /// Every goal of the card set, in the order they get called.
+    pub order: Vec<String>,
+    /// How many goals of `order` were called.
+    pub called: usize,
+}
+
+impl CallerGame {
+    pub fn called(&self) -> &[String] {
+        &self.order[..self.called.min(self.order.len())]
+    }
+
+    pub fn last_called(&self) -> Option<&String> {
+        self.called().last()
+    }
+
+    pub fn finished(&self) -> bool {
+        self.called >= self.order.len()
+    }
+}
+
+/// Name of line `idx` of [`GridSize::lines`].
+pub fn line_name(grid_size: GridSize, idx: usize) -> String {
+    let n = grid_size.side();
+
+    match idx {
+        idx if idx < n => format!("Row {}", idx + 1),
+        idx if idx < 2 * n => format!("Column {}", idx - n + 1),
+        idx if idx == 2 * n => "Diagonal".to_owned(),
+        _ => "Anti-diagonal".to_owned(),
+    }
+}
@@ -1,19 +1,58 @@
 use std::collections::HashMap;
 
+use strum::EnumIter;
+
+use crate::board::GridSize;
+
+#[derive(
+    Debug, Default, Clone, Copy, Eq, PartialEq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum ExportFormat {
+    #[default]
+    BingoSync,
+    LockoutLive,
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct BingoSyncCard {
     pub name: String,
 }
 
+impl BingoSyncCard {
+    /// Bingosync always shows a 5x5 board, smaller boards are centered with blank cards around
+    /// them.
+    pub fn board(grid_size: GridSize, cells: &[String]) -> Vec<BingoSyncCard> {
+        let (start, fill, end) = match grid_size {
+            GridSize::Size3 => (6, 2, 4),
+            GridSize::Size4 => (0, 1, 5),
+            _ => (0, 0, 0),
+        };
+        let blank = || BingoSyncCard {
+            name: " ".to_string(),
+        };
+
+        let mut cards: Vec<BingoSyncCard> = (0..start).map(|_| blank()).collect();
+        for row in cells.chunks_exact(grid_size.side()) {
+            cards.extend(row.iter().map(|name| BingoSyncCard {
+                name: name.to_owned(),
+            }));
+            cards.extend((0..fill).map(|_| blank()));
+        }
+        cards.extend((0..end).map(|_| blank()));
+
+        cards
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct LockoutLiveCard {
     pub goal: String,
     pub forced_positions: Vec<usize>,
-    range: Vec<String>,
+    pub range: Vec<String>,
     board_categories: Vec<String>,
     line_categories: Vec<String>,
     icons: Vec<String>,
-    progression: Vec<String>,
+    pub progression: Vec<String>,
 }
 
 impl Default for LockoutLiveCard {
@@ -45,6 +84,33 @@ pub struct LockoutLiveBoard {
     pub limits: HashMap<String, HashMap<String, usize>>,
 }
 
+impl LockoutLiveBoard {
+    /// Static board with every goal forced onto its cell.
+    pub fn from_cells(game_name: &str, cells: &[String]) -> Self {
+        let game_name = if game_name.is_empty() {
+            "None".to_owned()
+        } else {
+            game_name.to_owned()
+        };
+
+        Self {
+            schema_version: 3,
+            schema_mode: "relaxed".to_owned(),
+            game_name,
+            tag_names: Default::default(),
+            objectives: cells
+                .iter()
+                .enumerate()
+                .map(|(idx, v)| LockoutLiveCard::new(v.to_owned(), vec![idx + 1]))
+                .collect(),
+            limits: HashMap::from([
+                ("board".to_string(), HashMap::default()),
+                ("line".to_string(), HashMap::default()),
+            ]),
+        }
+    }
+}
+
 impl LockoutLiveCard {
     pub fn new(goal: String, forced_positions: Vec<usize>) -> Self {
         Self {
@@ -53,4 +119,14 @@ impl LockoutLiveCard {
             ..Default::default()
         }
     }
+
+    pub fn from_goal(goal: String, categories: Vec<String>, progression: Vec<String>) -> Self {
+        Self {
+            goal,
+            board_categories: categories.clone(),
+            line_categories: categories,
+            progression,
+            ..Default::default()
+        }
+    }
 }
@@ -0,0 +1,345 @@
+use std::{
+    fmt,
+    io::{Read, Write},
+};
+
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use strum::IntoEnumIterator;
+
+use crate::{
+    board::{DifficultyTarget, GenerateMode, GenerateOptions, GeneratedBoard, GridSize},
+    goal::{GoalParam, GoalPool},
+};
+
+const PREFIX: &str = "BG";
+const VERSION: u32 = 1;
+
+const KIND_GENERATED: u8 = 0;
+const KIND_CUSTOM: u8 = 1;
+
+/// Cell texts can contain newlines, so they're split on the ASCII unit separator.
+const CELL_SEPARATOR: char = '\u{1f}';
+
+const FNV_OFFSET: u32 = 0x811c_9dc5;
+const FNV_PRIME: u32 = 0x0100_0193;
+
+/// Short text that reproduces a board, `BG<version>-<base64>`.
+#[derive(Debug, Clone)]
+pub enum BoardCode {
+    /// Regenerated from the same goal pool with the same seed and options.
+    Generated {
+        pool_hash: u32,
+        grid_size: GridSize,
+        seed: u64,
+        mode: GenerateMode,
+        line_difficulty: Option<DifficultyTarget>,
+    },
+    /// Hand-made board, cell texts row-major.
+    Custom {
+        grid_size: GridSize,
+        cells: Vec<String>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    NotACode,
+    UnsupportedVersion(u32),
+    Malformed,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeError::NotACode => write!(f, "Not a board code"),
+            CodeError::UnsupportedVersion(v) => {
+                write!(f, "Board code version {v} isn't supported by this version")
+            }
+            CodeError::Malformed => write!(f, "Board code is damaged or incomplete"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+impl BoardCode {
+    pub fn generated(pool: &GoalPool, board: &GeneratedBoard) -> Self {
+        BoardCode::Generated {
+            pool_hash: pool_hash(pool),
+            grid_size: board.grid_size,
+            seed: board.seed,
+            mode: board.mode,
+            line_difficulty: board.line_difficulty,
+        }
+    }
+
+    pub fn grid_size(&self) -> GridSize {
+        match self {
+            BoardCode::Generated { grid_size, .. } | BoardCode::Custom { grid_size, .. } => {
+                *grid_size
+            }
+        }
+    }
+
+    /// Options that regenerate the board, `None` for hand-made boards.
+    pub fn options(&self) -> Option<GenerateOptions> {
+        let BoardCode::Generated {
+            grid_size,
+            seed,
+            mode,
+            line_difficulty,
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        Some(GenerateOptions {
+            grid_size: *grid_size,
+            seed: Some(*seed),
+            mode: *mode,
+            line_difficulty: *line_difficulty,
+            ..Default::default()
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::new();
+
+        match self {
+            BoardCode::Generated {
+                pool_hash,
+                grid_size,
+                seed,
+                mode,
+                line_difficulty,
+            } => {
+                bytes.push(KIND_GENERATED);
+                bytes.push(grid_size.side() as u8);
+                bytes.push(GenerateMode::iter().position(|m| m == *mode).unwrap_or(0) as u8);
+                bytes.extend(pool_hash.to_le_bytes());
+                bytes.extend(seed.to_le_bytes());
+
+                if let Some(target) = line_difficulty {
+                    bytes.extend((target.target as u16).to_le_bytes());
+                    bytes.extend((target.tolerance as u16).to_le_bytes());
+                }
+            }
+            BoardCode::Custom { grid_size, cells } => {
+                bytes.push(KIND_CUSTOM);
+                bytes.push(grid_size.side() as u8);
+
+                let text = cells
+                    .iter()
+                    .map(|c| c.trim())
+                    .collect::<Vec<_>>()
+                    .join(&CELL_SEPARATOR.to_string());
+                let mut encoder = DeflateEncoder::new(bytes, Compression::best());
+                // Writing into a Vec can't fail.
+                encoder.write_all(text.as_bytes()).unwrap();
+                bytes = encoder.finish().unwrap();
+            }
+        }
+
+        format!("{PREFIX}{VERSION}-{}", URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    pub fn decode(code: &str) -> Result<Self, CodeError> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        let (header, data) = code.split_once('-').ok_or(CodeError::NotACode)?;
+        let version: u32 = header
+            .strip_prefix(PREFIX)
+            .and_then(|v| v.parse().ok())
+            .ok_or(CodeError::NotACode)?;
+        if version != VERSION {
+            return Err(CodeError::UnsupportedVersion(version));
+        }
+
+        let bytes = URL_SAFE_NO_PAD
+            .decode(data)
+            .map_err(|_| CodeError::Malformed)?;
+        let [kind, side, rest @ ..] = bytes.as_slice() else {
+            return Err(CodeError::Malformed);
+        };
+        let grid_size = GridSize::from_repr(*side as usize).ok_or(CodeError::Malformed)?;
+
+        match *kind {
+            KIND_GENERATED => {
+                if rest.len() != 13 && rest.len() != 17 {
+                    return Err(CodeError::Malformed);
+                }
+
+                let mode = GenerateMode::iter()
+                    .nth(rest[0] as usize)
+                    .ok_or(CodeError::Malformed)?;
+                let pool_hash = u32::from_le_bytes(rest[1..5].try_into().unwrap());
+                let seed = u64::from_le_bytes(rest[5..13].try_into().unwrap());
+                let line_difficulty = (rest.len() == 17).then(|| DifficultyTarget {
+                    target: u16::from_le_bytes([rest[13], rest[14]]) as usize,
+                    tolerance: u16::from_le_bytes([rest[15], rest[16]]) as usize,
+                });
+
+                Ok(BoardCode::Generated {
+                    pool_hash,
+                    grid_size,
+                    seed,
+                    mode,
+                    line_difficulty,
+                })
+            }
+            KIND_CUSTOM => {
+                let mut text = String::new();
+                DeflateDecoder::new(rest)
+                    .read_to_string(&mut text)
+                    .map_err(|_| CodeError::Malformed)?;
+
+                let cells: Vec<String> = text.split(CELL_SEPARATOR).map(str::to_owned).collect();
+                if cells.len() != grid_size.cell_count() {
+                    return Err(CodeError::Malformed);
+                }
+
+                Ok(BoardCode::Custom { grid_size, cells })
+            }
+            _ => Err(CodeError::Malformed),
+        }
+    }
+}
+
+/// FNV-1a over everything in the pool that changes what gets generated, so a code can tell
+/// whether it's used with the pool it was made from.
+pub fn pool_hash(pool: &GoalPool) -> u32 {
+    let mut hash = FNV_OFFSET;
+    let mut feed = |bytes: &[u8]| {
+        for b in bytes.iter().chain([0xff].iter()) {
+            hash ^= *b as u32;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+
+    for goal in &pool.goals {
+        feed(goal.name.trim().as_bytes());
+        feed(&(goal.difficulty as u64).to_le_bytes());
+        for tag in goal
+            .categories
+            .iter()
+            .chain(&goal.exclusions)
+            .chain(&goal.synergies)
+        {
+            feed(tag.as_bytes());
+        }
+        // Left out when unset so pools from before weights keep their hash.
+        if let Some(weight) = goal.weight {
+            feed(&weight.to_bits().to_le_bytes());
+        }
+        for (key, param) in &goal.params {
+            feed(key.as_bytes());
+            for value in param.values() {
+                feed(value.as_bytes());
+            }
+            if let GoalParam::Range {
+                difficulty_step, ..
+            } = param
+            {
+                feed(&difficulty_step.to_bits().to_le_bytes());
+            }
+        }
+    }
+
+    for limits in [
+        &pool.board_limits,
+        &pool.line_limits,
+        &pool.board_minimums,
+        &pool.line_minimums,
+    ] {
+        let mut limits: Vec<(&String, &usize)> = limits.iter().collect();
+        limits.sort();
+        for (category, limit) in limits {
+            feed(category.as_bytes());
+            feed(&(*limit as u64).to_le_bytes());
+        }
+    }
+
+    for set in &pool.line_exclusions {
+        for name in set {
+            feed(name.trim().as_bytes());
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_round_trip() {
+        let code = BoardCode::Generated {
+            pool_hash: 0xdead_beef,
+            grid_size: GridSize::Size7,
+            seed: u64::MAX - 1,
+            mode: GenerateMode::Constrained,
+            line_difficulty: Some(DifficultyTarget {
+                target: 300,
+                tolerance: 4,
+            }),
+        };
+
+        let BoardCode::Generated {
+            pool_hash,
+            grid_size,
+            seed,
+            mode,
+            line_difficulty: Some(target),
+        } = BoardCode::decode(&code.encode()).unwrap()
+        else {
+            panic!("decoded into a different board code");
+        };
+        assert_eq!(pool_hash, 0xdead_beef);
+        assert_eq!(grid_size, GridSize::Size7);
+        assert_eq!(seed, u64::MAX - 1);
+        assert_eq!(mode, GenerateMode::Constrained);
+        assert_eq!((target.target, target.tolerance), (300, 4));
+    }
+
+    #[test]
+    fn custom_round_trip() {
+        let cells: Vec<String> = (0..9).map(|i| format!("Goal {i}\nsecond line")).collect();
+        let code = BoardCode::Custom {
+            grid_size: GridSize::Size3,
+            cells: cells.clone(),
+        };
+
+        let BoardCode::Custom {
+            grid_size,
+            cells: decoded,
+        } = BoardCode::decode(&code.encode()).unwrap()
+        else {
+            panic!("decoded into a different board code");
+        };
+        assert_eq!(grid_size, GridSize::Size3);
+        assert_eq!(decoded, cells);
+    }
+
+    #[test]
+    fn rejects_bad_prefix_and_version() {
+        let code = BoardCode::Custom {
+            grid_size: GridSize::Size3,
+            cells: vec![String::new(); 9],
+        }
+        .encode();
+        let (_, data) = code.split_once('-').unwrap();
+
+        assert_eq!(
+            BoardCode::decode(&format!("XX1-{data}")).unwrap_err(),
+            CodeError::NotACode
+        );
+        assert_eq!(BoardCode::decode(data).unwrap_err(), CodeError::NotACode);
+        assert_eq!(
+            BoardCode::decode(&format!("BG2-{data}")).unwrap_err(),
+            CodeError::UnsupportedVersion(2)
+        );
+        assert_eq!(BoardCode::decode("BG1-").unwrap_err(), CodeError::Malformed);
+    }
+}
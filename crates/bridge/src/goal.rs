@@ -0,0 +1,272 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Templates with more combinations than this aren't matched back from board text.
+const MAX_TEMPLATE_INSTANCES: usize = 10_000;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct Goal {
+    pub name: String,
+    #[serde(default)]
+    pub difficulty: usize,
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Only one goal of each exclusion group can be on a board.
+    #[serde(default)]
+    pub exclusions: Vec<String>,
+    /// Goals sharing a synergy tag can't be in the same line.
+    #[serde(default)]
+    pub synergies: Vec<String>,
+    /// Relative chance of being picked when generating, 1 when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// Values for the `{key}` placeholders in the name, picked when a board is generated.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, GoalParam>,
+    /// Name of the template this goal was expanded from.
+    #[serde(skip)]
+    pub template: Option<String>,
+}
+
+impl Goal {
+    pub fn is_template(&self) -> bool {
+        !self.params.is_empty()
+    }
+
+    pub fn weight(&self) -> f64 {
+        self.weight.unwrap_or(1.0).max(0.0)
+    }
+
+    /// Name the goal has in the pool, the template name for expanded goals.
+    pub fn pool_name(&self) -> &str {
+        self.template.as_deref().unwrap_or(&self.name).trim()
+    }
+
+    /// Fills in every placeholder with the value at the given index of its parameter, the
+    /// difficulty goes up by each range's `difficulty_step` per step above its minimum.
+    pub fn instantiate(&self, picks: &BTreeMap<String, usize>) -> Goal {
+        let mut name = self.name.clone();
+        let mut difficulty = self.difficulty as f64;
+
+        for (key, param) in &self.params {
+            let idx = picks.get(key).copied().unwrap_or(0);
+            let values = param.values();
+            let Some(value) = values.get(idx).or(values.last()) else {
+                continue;
+            };
+
+            name = name.replace(&format!("{{{key}}}"), value);
+            if let GoalParam::Range {
+                difficulty_step, ..
+            } = param
+            {
+                difficulty += idx.min(values.len() - 1) as f64 * difficulty_step;
+            }
+        }
+
+        Goal {
+            name,
+            difficulty: difficulty.round().max(0.0) as usize,
+            params: BTreeMap::new(),
+            template: Some(self.name.trim().to_owned()),
+            ..self.clone()
+        }
+    }
+
+    /// Finds the expansion of this template that reads `name`.
+    pub fn instance_named(&self, name: &str) -> Option<Goal> {
+        let name = name.trim();
+        let params: Vec<(&String, usize)> = self
+            .params
+            .iter()
+            .map(|(key, param)| (key, param.values().len()))
+            .collect();
+
+        let combinations = params
+            .iter()
+            .try_fold(1usize, |acc, (_, len)| acc.checked_mul(*len))?;
+        if combinations == 0 || combinations > MAX_TEMPLATE_INSTANCES {
+            return None;
+        }
+
+        (0..combinations).find_map(|mut combination| {
+            let picks = params
+                .iter()
+                .map(|(key, len)| {
+                    let idx = combination % len;
+                    combination /= len;
+                    ((*key).clone(), idx)
+                })
+                .collect();
+
+            let goal = self.instantiate(&picks);
+            (goal.name.trim() == name).then_some(goal)
+        })
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum GoalParam {
+    /// Every `step`th number from `min` to `max`.
+    Range {
+        min: usize,
+        max: usize,
+        #[serde(default = "default_step")]
+        step: usize,
+        /// Difficulty added for every step above `min`.
+        #[serde(default)]
+        difficulty_step: f64,
+    },
+    Choice(Vec<String>),
+}
+
+fn default_step() -> usize {
+    1
+}
+
+impl GoalParam {
+    pub fn values(&self) -> Vec<String> {
+        match self {
+            GoalParam::Range { min, max, step, .. } => (*min..=*max)
+                .step_by((*step).max(1))
+                .map(|v| v.to_string())
+                .collect(),
+            GoalParam::Choice(choices) => choices.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct GoalPool {
+    #[serde(default)]
+    pub game_name: String,
+    pub goals: Vec<Goal>,
+    #[serde(default)]
+    pub board_limits: HashMap<String, usize>,
+    #[serde(default)]
+    pub line_limits: HashMap<String, usize>,
+    #[serde(default)]
+    pub board_minimums: HashMap<String, usize>,
+    #[serde(default)]
+    pub line_minimums: HashMap<String, usize>,
+    /// Sets of goal names, no two goals of a set can be in the same line.
+    #[serde(default)]
+    pub line_exclusions: Vec<Vec<String>>,
+}
+
+impl GoalPool {
+    pub fn filtered(&self, filter: &GoalFilter) -> Vec<&Goal> {
+        self.goals.iter().filter(|g| filter.matches(g)).collect()
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Goal> {
+        let name = name.trim();
+        self.goals.iter().find(|g| g.name.trim() == name)
+    }
+
+    /// Like [`GoalPool::find`], but also matches text expanded from a template.
+    pub fn resolve(&self, name: &str) -> Option<Goal> {
+        self.find(name).cloned().or_else(|| {
+            self.goals
+                .iter()
+                .filter(|g| g.is_template())
+                .find_map(|g| g.instance_named(name))
+        })
+    }
+
+    pub fn categories(&self) -> Vec<String> {
+        let mut categories: Vec<String> = self
+            .goals
+            .iter()
+            .flat_map(|g| g.categories.iter().cloned())
+            .collect();
+        categories.sort();
+        categories.dedup();
+        categories
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GoalFilter {
+    /// Goal has to have at least one of these, empty means any category.
+    pub categories: Vec<String>,
+    pub min_difficulty: Option<usize>,
+    pub max_difficulty: Option<usize>,
+}
+
+impl GoalFilter {
+    pub fn matches(&self, goal: &Goal) -> bool {
+        if self.min_difficulty.is_some_and(|min| goal.difficulty < min) {
+            return false;
+        }
+
+        if self.max_difficulty.is_some_and(|max| goal.difficulty > max) {
+            return false;
+        }
+
+        self.categories.is_empty() || goal.categories.iter().any(|c| self.categories.contains(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> Goal {
+        Goal {
+            name: "Collect {n} {gem} gems".to_owned(),
+            difficulty: 2,
+            params: BTreeMap::from([
+                (
+                    "n".to_owned(),
+                    GoalParam::Range {
+                        min: 3,
+                        max: 7,
+                        step: 2,
+                        difficulty_step: 1.5,
+                    },
+                ),
+                (
+                    "gem".to_owned(),
+                    GoalParam::Choice(vec!["red".to_owned(), "blue".to_owned()]),
+                ),
+            ]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn range_values_follow_step() {
+        assert_eq!(template().params["n"].values(), ["3", "5", "7"]);
+    }
+
+    #[test]
+    fn instantiate_fills_placeholders_and_scales_difficulty() {
+        let goal = template().instantiate(&BTreeMap::from([
+            ("n".to_owned(), 2),
+            ("gem".to_owned(), 1),
+        ]));
+
+        assert_eq!(goal.name, "Collect 7 blue gems");
+        assert_eq!(goal.difficulty, 5);
+        assert!(!goal.is_template());
+        assert_eq!(goal.pool_name(), "Collect {n} {gem} gems");
+
+        let lowest = template().instantiate(&BTreeMap::new());
+        assert_eq!(lowest.name, "Collect 3 red gems");
+        assert_eq!(lowest.difficulty, 2);
+    }
+
+    #[test]
+    fn resolves_expanded_names() {
+        let pool = GoalPool {
+            goals: vec![template()],
+            ..Default::default()
+        };
+
+        let goal = pool.resolve(" Collect 5 red gems ").unwrap();
+        assert_eq!(goal.difficulty, 4);
+        assert_eq!(goal.template.as_deref(), Some("Collect {n} {gem} gems"));
+        assert!(pool.resolve("Collect 4 red gems").is_none());
+    }
+}
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, fmt};
+
+use crate::goal::{Goal, GoalPool};
+
+/// Goal pool read from a plain text or Markdown list, with the lines that didn't make it in.
+#[derive(Debug, Clone, Default)]
+pub struct GoalList {
+    pub pool: GoalPool,
+    pub errors: Vec<LineError>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// Counting from 1.
+    pub line: usize,
+    pub reason: LineErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineErrorReason {
+    UnclosedDifficulty,
+    BadDifficulty(String),
+    EmptyTag,
+    NoText,
+    Duplicate { first_line: usize },
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+
+        match &self.reason {
+            LineErrorReason::UnclosedDifficulty => write!(f, "'[' without a closing ']'"),
+            LineErrorReason::BadDifficulty(value) => {
+                write!(f, "'[{value}]' isn't a difficulty, use a whole number")
+            }
+            LineErrorReason::EmptyTag => write!(f, "'#' without a tag name"),
+            LineErrorReason::NoText => write!(f, "goal has no text"),
+            LineErrorReason::Duplicate { first_line } => {
+                write!(f, "same goal as line {first_line}")
+            }
+        }
+    }
+}
+
+/// Reads one goal per line, like `[3] #boss #early Defeat X`. The difficulty in brackets and the
+/// `#` tags, which become categories, are optional. List bullets and numbering are dropped,
+/// blank lines and Markdown headings are skipped and the first heading names the game.
+pub fn parse_goal_list(text: &str) -> GoalList {
+    let mut list = GoalList::default();
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+
+    for (idx, line) in text.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(heading) = heading(line) {
+            if list.pool.game_name.is_empty() {
+                list.pool.game_name = heading.to_owned();
+            }
+            continue;
+        }
+
+        match parse_line(strip_bullet(line)) {
+            Ok(goal) => match first_lines.get(&goal.name) {
+                Some(&first_line) => list.errors.push(LineError {
+                    line: line_number,
+                    reason: LineErrorReason::Duplicate { first_line },
+                }),
+                None => {
+                    first_lines.insert(goal.name.clone(), line_number);
+                    list.pool.goals.push(goal);
+                }
+            },
+            Err(reason) => list.errors.push(LineError {
+                line: line_number,
+                reason,
+            }),
+        }
+    }
+
+    list
+}
+
+/// Text of a Markdown heading. It needs a space after the `#`s, `#boss` is a tag.
+fn heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    if text.len() == line.len() || !(text.is_empty() || text.starts_with(' ')) {
+        return None;
+    }
+
+    Some(text.trim())
+}
+
+/// Drops `-`, `*` and `+` bullets, `1.` or `1)` numbering and `[ ]` task boxes.
+fn strip_bullet(line: &str) -> &str {
+    let line = match line.strip_prefix(['-', '*', '+']) {
+        Some(rest) if rest.starts_with(' ') => rest.trim_start(),
+        _ => {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            match line[digits..].strip_prefix(['.', ')']) {
+                Some(rest) if digits > 0 && rest.starts_with(' ') => rest.trim_start(),
+                _ => line,
+            }
+        }
+    };
+
+    for task_box in ["[ ] ", "[x] ", "[X] "] {
+        if let Some(rest) = line.strip_prefix(task_box) {
+            return rest.trim_start();
+        }
+    }
+
+    line
+}
+
+fn parse_line(line: &str) -> Result<Goal, LineErrorReason> {
+    let mut rest = line;
+
+    let mut difficulty = 0;
+    if let Some(after) = rest.strip_prefix('[') {
+        let (value, after) = after
+            .split_once(']')
+            .ok_or(LineErrorReason::UnclosedDifficulty)?;
+        difficulty = value
+            .trim()
+            .parse()
+            .map_err(|_| LineErrorReason::BadDifficulty(value.trim().to_owned()))?;
+        rest = after.trim_start();
+    }
+
+    let mut categories: Vec<String> = Vec::new();
+    while let Some(after) = rest.strip_prefix('#') {
+        let (tag, after) = after.split_once(char::is_whitespace).unwrap_or((after, ""));
+        if tag.is_empty() {
+            return Err(LineErrorReason::EmptyTag);
+        }
+        if !categories.iter().any(|c| c == tag) {
+            categories.push(tag.to_owned());
+        }
+        rest = after.trim_start();
+    }
+
+    let name = rest.trim();
+    if name.is_empty() {
+        return Err(LineErrorReason::NoText);
+    }
+
+    Ok(Goal {
+        name: name.to_owned(),
+        difficulty,
+        categories,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_markdown_list() {
+        let list = parse_goal_list(
+            "# Super Game\n\
+             \n\
+             ## Early\n\
+             - [3] #boss #early Defeat the first boss\n\
+             * [ ] Collect 10 coins\n\
+             12. [x] [ 5 ] #item #item Find the hookshot\n\
+             + #boss\tBeat the #2 boss\n",
+        );
+
+        assert!(list.errors.is_empty(), "{:?}", list.errors);
+        assert_eq!(list.pool.game_name, "Super Game");
+
+        let goals: Vec<_> = list
+            .pool
+            .goals
+            .iter()
+            .map(|g| (g.name.as_str(), g.difficulty, g.categories.join(",")))
+            .collect();
+        assert_eq!(
+            goals,
+            [
+                ("Defeat the first boss", 3, "boss,early".to_owned()),
+                ("Collect 10 coins", 0, String::new()),
+                ("Find the hookshot", 5, "item".to_owned()),
+                ("Beat the #2 boss", 0, "boss".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_plain_list() {
+        let list = parse_goal_list("Reach the castle\r\n1) Open 3 chests\r\n-not a bullet\r\n");
+
+        assert!(list.errors.is_empty());
+        assert!(list.pool.game_name.is_empty());
+        let names: Vec<_> = list.pool.goals.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Reach the castle", "Open 3 chests", "-not a bullet"]
+        );
+    }
+
+    #[test]
+    fn reports_bad_lines() {
+        let list = parse_goal_list(
+            "[2 Unclosed\n\
+             [two] Not a number\n\
+             # \n\
+             # Ok\n\
+             - # Empty tag\n\
+             - [1] #tag\n\
+             Goal\n\
+             - Goal\n",
+        );
+
+        assert_eq!(list.pool.game_name, "Ok");
+        assert_eq!(list.pool.goals.len(), 1);
+        assert_eq!(
+            list.errors,
+            [
+                LineError {
+                    line: 1,
+                    reason: LineErrorReason::UnclosedDifficulty
+                },
+                LineError {
+                    line: 2,
+                    reason: LineErrorReason::BadDifficulty("two".to_owned())
+                },
+                LineError {
+                    line: 5,
+                    reason: LineErrorReason::EmptyTag
+                },
+                LineError {
+                    line: 6,
+                    reason: LineErrorReason::NoText
+                },
+                LineError {
+                    line: 8,
+                    reason: LineErrorReason::Duplicate { first_line: 7 }
+                },
+            ]
+        );
+        assert_eq!(list.errors[4].to_string(), "Line 8: same goal as line 7");
+    }
+}
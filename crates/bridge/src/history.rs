@@ -0,0 +1,126 @@
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::goal::GoalPool;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Goals of every exported board, oldest first.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct UsageHistory {
+    #[serde(default)]
+    pub boards: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HistoryEntry {
+    /// Unix timestamp in seconds.
+    pub exported_at: u64,
+    pub file: String,
+    /// Goal texts as exported.
+    pub goals: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryWindow {
+    Boards(usize),
+    Days(u64),
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AvoidMode {
+    /// Recently used goals are less likely to be picked, the more often they were used the less.
+    #[default]
+    DownWeight,
+    /// Recently used goals are never picked.
+    Exclude,
+}
+
+/// Goals to keep off a generated board.
+#[derive(Debug, Clone, Default)]
+pub struct AvoidRecent {
+    pub mode: AvoidMode,
+    /// How often each goal was used, by pool name.
+    pub uses: HashMap<String, usize>,
+}
+
+impl UsageHistory {
+    pub fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default()
+    }
+
+    /// How often each goal of `pool` was exported within `window`, all boards when empty. Text
+    /// expanded from a template counts for the template.
+    pub fn uses(&self, pool: &GoalPool, window: Option<HistoryWindow>) -> HashMap<String, usize> {
+        let boards = match window {
+            None => &self.boards[..],
+            Some(HistoryWindow::Boards(count)) => {
+                &self.boards[self.boards.len().saturating_sub(count)..]
+            }
+            Some(HistoryWindow::Days(days)) => {
+                let since = Self::now().saturating_sub(days.saturating_mul(SECONDS_PER_DAY));
+                let first = self.boards.partition_point(|b| b.exported_at < since);
+                &self.boards[first..]
+            }
+        };
+
+        let mut names: HashMap<&str, Option<String>> = HashMap::new();
+        let mut uses = HashMap::new();
+        for text in boards.iter().flat_map(|b| &b.goals) {
+            let name = names
+                .entry(text.trim())
+                .or_insert_with(|| pool.resolve(text).map(|goal| goal.pool_name().to_owned()));
+            if let Some(name) = name {
+                *uses.entry(name.clone()).or_default() += 1;
+            }
+        }
+
+        uses
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goal::Goal;
+
+    #[test]
+    fn uses_counts_within_window() {
+        let pool = GoalPool {
+            goals: ["A", "B"]
+                .map(|name| Goal {
+                    name: name.to_owned(),
+                    ..Default::default()
+                })
+                .to_vec(),
+            ..Default::default()
+        };
+        let board = |days_ago: u64, goals: &[&str]| HistoryEntry {
+            exported_at: UsageHistory::now() - days_ago * SECONDS_PER_DAY,
+            file: String::new(),
+            goals: goals.iter().map(|g| g.to_string()).collect(),
+        };
+        let history = UsageHistory {
+            boards: vec![board(10, &["A", "B", "Gone"]), board(1, &[" A "])],
+        };
+
+        let uses = history.uses(&pool, None);
+        assert_eq!(uses["A"], 2);
+        assert_eq!(uses["B"], 1);
+        assert!(!uses.contains_key("Gone"));
+
+        let uses = history.uses(&pool, Some(HistoryWindow::Boards(1)));
+        assert_eq!(uses.get("B"), None);
+
+        let uses = history.uses(&pool, Some(HistoryWindow::Days(5)));
+        assert_eq!(uses.get("A"), Some(&1));
+
+        let uses = history.uses(&pool, Some(HistoryWindow::Days(u64::MAX)));
+        assert_eq!(uses.get("A"), Some(&2));
+    }
+}
@@ -0,0 +1,35 @@
+use strum::EnumIter;
+
+use crate::board::GridSize;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Svg => "svg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BoardImage {
+    /// Drawn above the grid, left out when empty.
+    pub title: String,
+    pub grid_size: GridSize,
+    /// Row-major, `grid_size.cell_count()` long.
+    pub cells: Vec<ImageCell>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImageCell {
+    pub text: String,
+    /// Cells of the same category share a background color, plain when empty.
+    pub category: Option<String>,
+}
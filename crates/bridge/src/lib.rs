@@ -1,3 +1,16 @@
+pub mod board;
+pub mod caller;
 pub mod card;
+pub mod code;
+pub mod goal;
+pub mod goal_list;
 pub mod handle;
+pub mod history;
+pub mod image;
 pub mod message;
+pub mod metadata;
+pub mod print;
+pub mod project;
+pub mod rules;
+pub mod table;
+pub mod tournament;
@@ -1,11 +1,118 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
-use crate::card::{BingoSyncCard, LockoutLiveBoard};
+use crate::{
+    board::{BulkOptions, GenerateOptions, GeneratedBoard, GridSize},
+    caller::{CallerGame, CardSet},
+    card::{BingoSyncCard, LockoutLiveBoard},
+    goal::{Goal, GoalFilter, GoalPool},
+    history::UsageHistory,
+    image::{BoardImage, ImageFormat},
+    metadata::ExportMetadata,
+    print::PrintOptions,
+    project::BingoProject,
+    table::{ColumnMapping, Delimiter},
+    tournament::{FairnessReport, PlanOptions},
+};
 
 #[derive(Debug)]
 pub enum MessageToBackend {
-    CreateBingoSyncFile { data: Vec<BingoSyncCard> },
-    CreateLockoutLiveFile { data: LockoutLiveBoard },
+    CreateBingoSyncFile {
+        data: Vec<BingoSyncCard>,
+        metadata: Option<ExportMetadata>,
+    },
+    CreateLockoutLiveFile {
+        data: LockoutLiveBoard,
+        metadata: Option<ExportMetadata>,
+    },
+    CreateBoardImage {
+        image: BoardImage,
+        format: ImageFormat,
+    },
+    CreateHtmlBoard {
+        board: BoardImage,
+        /// Board stays covered until clicked.
+        spoiler: bool,
+    },
+    CreateBingoSyncPoolFile {
+        pool: GoalPool,
+        filter: GoalFilter,
+        seed: Option<u64>,
+    },
+    CreateLockoutLivePoolFile {
+        pool: GoalPool,
+        filter: GoalFilter,
+    },
+    LoadGoalPool {
+        path: PathBuf,
+    },
+    ImportPoolTable {
+        path: PathBuf,
+        mapping: ColumnMapping,
+    },
+    ImportBoardTable {
+        path: PathBuf,
+    },
+    ImportGoalList {
+        path: PathBuf,
+    },
+    OpenProject {
+        path: PathBuf,
+    },
+    /// Saved into the export folder when `path` is empty.
+    SaveProject {
+        path: Option<PathBuf>,
+        project: BingoProject,
+    },
+    CreatePoolTableFile {
+        pool: GoalPool,
+        filter: GoalFilter,
+        delimiter: Delimiter,
+    },
+    CreateBoardTableFile {
+        grid_size: GridSize,
+        cells: Vec<String>,
+        delimiter: Delimiter,
+    },
+    GenerateBoard {
+        pool: GoalPool,
+        options: GenerateOptions,
+    },
+    RerollCells {
+        pool: GoalPool,
+        grid_size: GridSize,
+        board: Vec<Goal>,
+        cells: Vec<usize>,
+    },
+    VerifyExport {
+        path: PathBuf,
+    },
+    BulkGenerate {
+        pool: GoalPool,
+        pool_path: Option<PathBuf>,
+        options: BulkOptions,
+    },
+    CreatePdfCards {
+        pool: GoalPool,
+        options: PrintOptions,
+    },
+    OpenCardSet {
+        path: PathBuf,
+    },
+    RestartCallerGame {
+        path: PathBuf,
+        seed: Option<u64>,
+    },
+    SaveCallerGame {
+        path: PathBuf,
+        game: CallerGame,
+    },
+    CreateCallerSheet {
+        path: PathBuf,
+    },
+    PlanTournament {
+        pool: GoalPool,
+        options: PlanOptions,
+    },
 }
 
 #[derive(Debug)]
@@ -14,6 +121,41 @@ pub enum MessageToFrontend {
         notification_type: NotificationType,
         message: Arc<str>,
     },
+    GoalPoolLoaded {
+        /// Empty for pools imported from another format, only JSON pools can be loaded again.
+        path: Option<PathBuf>,
+        pool: Box<GoalPool>,
+    },
+    BoardGenerated {
+        board: GeneratedBoard,
+    },
+    BoardImported {
+        grid_size: GridSize,
+        cells: Vec<String>,
+    },
+    ProjectOpened {
+        path: PathBuf,
+        project: Box<BingoProject>,
+    },
+    ProjectSaved {
+        path: PathBuf,
+    },
+    BulkProgress {
+        done: usize,
+        total: usize,
+    },
+    BulkFinished,
+    TournamentPlanned {
+        report: FairnessReport,
+    },
+    CallerGameLoaded {
+        path: PathBuf,
+        card_set: Box<CardSet>,
+        game: CallerGame,
+    },
+    UsageHistoryLoaded {
+        history: UsageHistory,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
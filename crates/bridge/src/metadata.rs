@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use crate::board::{BoardConstraints, GenerateMode, GenerateOptions, GridSize};
+
+/// Written next to an export as `<export>.meta.json`, has everything needed to regenerate the
+/// board.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ExportMetadata {
+    #[serde(default)]
+    pub generator_version: String,
+    #[serde(default)]
+    pub exported_at: String,
+    #[serde(default)]
+    pub export_file: String,
+    pub pool_path: Option<PathBuf>,
+    /// SHA-256 of the goal pool file.
+    pub pool_hash: Option<String>,
+    pub grid_size: GridSize,
+    /// Empty for hand-made boards.
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub mode: GenerateMode,
+    #[serde(default)]
+    pub constraints: BoardConstraints,
+    /// Cells were rerolled after generating.
+    #[serde(default)]
+    pub rerolled: bool,
+    /// Recently used goals were avoided, the board depends on the usage history at the time.
+    #[serde(default)]
+    pub avoided_recent: bool,
+}
+
+impl ExportMetadata {
+    /// Options that regenerate the board, `None` for hand-made boards.
+    pub fn options(&self) -> Option<GenerateOptions> {
+        Some(GenerateOptions {
+            grid_size: self.grid_size,
+            seed: Some(self.seed?),
+            mode: self.mode,
+            line_difficulty: self.constraints.line_difficulty,
+            pinned: self.constraints.pinned.clone(),
+            avoid_recent: None,
+        })
+    }
+}
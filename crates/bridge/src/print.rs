@@ -0,0 +1,37 @@
+use strum::EnumIter;
+
+use crate::board::GenerateOptions;
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter, serde::Serialize, serde::Deserialize,
+)]
+pub enum PaperSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PaperSize {
+    /// Width and height in millimeters, portrait.
+    pub fn size_mm(self) -> (f32, f32) {
+        match self {
+            PaperSize::A4 => (210.0, 297.0),
+            PaperSize::Letter => (215.9, 279.4),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PrintOptions {
+    pub count: usize,
+    pub title: String,
+    /// Cards use the seeds from here on, random when empty.
+    pub first_seed: Option<u64>,
+    /// Seed and pinned goals are ignored.
+    pub generate: GenerateOptions,
+    pub paper: PaperSize,
+    /// 1, 2 or 4.
+    pub cards_per_page: usize,
+    /// Center cell is a free space, odd grid sizes only.
+    pub free_center: bool,
+}
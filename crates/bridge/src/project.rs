@@ -0,0 +1,122 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use crate::{
+    board::{BoardConstraints, GenerateMode, GridSize},
+    card::{BingoSyncCard, LockoutLiveBoard, LockoutLiveCard},
+    goal::{Goal, GoalPool},
+};
+
+pub const PROJECT_EXTENSION: &str = "bingo";
+
+/// Bumped whenever the saved layout changes, older files are migrated when opened.
+pub const PROJECT_SCHEMA_VERSION: u32 = 1;
+
+/// Everything about a board, saved as a `.bingo` file. Exports are derived from it.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct BingoProject {
+    pub schema_version: u32,
+    #[serde(default)]
+    pub generator_version: String,
+    #[serde(default)]
+    pub saved_at: String,
+    #[serde(default)]
+    pub game_name: String,
+    pub grid_size: GridSize,
+    /// Row-major, `grid_size.cell_count()` long.
+    pub cells: Vec<ProjectCell>,
+    /// Empty for hand-made boards.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub mode: GenerateMode,
+    #[serde(default)]
+    pub constraints: BoardConstraints,
+    /// Cells were rerolled or edited after generating.
+    #[serde(default)]
+    pub rerolled: bool,
+    #[serde(default)]
+    pub lockout_limits: LockoutLimits,
+    #[serde(default)]
+    pub pool_path: Option<PathBuf>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct ProjectCell {
+    /// Goal on the cell, only the name is set for text that isn't from the pool.
+    pub goal: Goal,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub notes: String,
+}
+
+/// Most goals of a category lockout.live allows on the board and in a line.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct LockoutLimits {
+    pub board: BTreeMap<String, usize>,
+    pub line: BTreeMap<String, usize>,
+}
+
+impl LockoutLimits {
+    pub fn from_pool(pool: &GoalPool) -> Self {
+        Self {
+            board: pool
+                .board_limits
+                .iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+            line: pool
+                .line_limits
+                .iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+        }
+    }
+}
+
+impl BingoProject {
+    pub fn cell_texts(&self) -> Vec<String> {
+        self.cells.iter().map(|c| c.goal.name.clone()).collect()
+    }
+
+    pub fn bingo_sync_cards(&self) -> Vec<BingoSyncCard> {
+        BingoSyncCard::board(self.grid_size, &self.cell_texts())
+    }
+
+    /// Static board with every goal forced onto its cell, tagged with its categories so the
+    /// limits apply.
+    pub fn lockout_board(&self) -> LockoutLiveBoard {
+        let mut board = LockoutLiveBoard::from_cells(&self.game_name, &self.cell_texts());
+
+        let progression = LockoutLiveCard::default().progression;
+        board.objectives = self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| {
+                let mut card = LockoutLiveCard::from_goal(
+                    cell.goal.name.clone(),
+                    cell.goal.categories.clone(),
+                    progression.clone(),
+                );
+                card.forced_positions = vec![idx + 1];
+                card
+            })
+            .collect();
+
+        for (kind, limits) in [
+            ("board", &self.lockout_limits.board),
+            ("line", &self.lockout_limits.line),
+        ] {
+            board.limits.insert(
+                kind.to_owned(),
+                limits.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+            );
+        }
+
+        board
+    }
+}
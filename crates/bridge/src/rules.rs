@@ -0,0 +1,100 @@
+use std::fmt;
+
+use crate::{
+    board::GridSize,
+    goal::{Goal, GoalPool},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    Exclusion(String),
+    Synergy(String),
+    SameLine,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    pub rule: Rule,
+    /// Row-major board indices of the goals breaking the rule.
+    pub cells: Vec<usize>,
+}
+
+impl fmt::Display for RuleViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.rule {
+            Rule::Exclusion(group) => {
+                write!(f, "More than one goal from exclusion group '{group}'")
+            }
+            Rule::Synergy(tag) => write!(f, "Goals with synergy '{tag}' share a line"),
+            Rule::SameLine => write!(f, "Goals that can't share a line are in the same line"),
+        }
+    }
+}
+
+/// Both goals can't be on the same board, goals expanded from the same template count as one.
+pub fn excludes(a: &Goal, b: &Goal) -> bool {
+    a.exclusions.iter().any(|e| b.exclusions.contains(e))
+        || (a.template.is_some() && a.template == b.template)
+}
+
+/// Both goals can't be in the same line.
+pub fn conflicts(pool: &GoalPool, a: &Goal, b: &Goal) -> bool {
+    line_rule(pool, a, b).is_some()
+}
+
+fn line_rule(pool: &GoalPool, a: &Goal, b: &Goal) -> Option<Rule> {
+    if let Some(tag) = a.synergies.iter().find(|s| b.synergies.contains(s)) {
+        return Some(Rule::Synergy(tag.clone()));
+    }
+
+    let (a, b) = (a.pool_name(), b.pool_name());
+    pool.line_exclusions
+        .iter()
+        .any(|set| set.iter().any(|n| n.trim() == a) && set.iter().any(|n| n.trim() == b))
+        .then_some(Rule::SameLine)
+}
+
+/// Checks a board against the pool rules, cells are matched to pool goals by name and cells
+/// without a matching goal are skipped.
+pub fn check_board(pool: &GoalPool, grid_size: GridSize, names: &[String]) -> Vec<RuleViolation> {
+    let goals: Vec<Option<Goal>> = names.iter().map(|n| pool.resolve(n)).collect();
+    let mut violations: Vec<RuleViolation> = Vec::new();
+
+    let mut groups: Vec<(&String, Vec<usize>)> = Vec::new();
+    for (idx, goal) in goals.iter().enumerate() {
+        for group in goal.iter().flat_map(|g| g.exclusions.iter()) {
+            match groups.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, cells)) => cells.push(idx),
+                None => groups.push((group, vec![idx])),
+            }
+        }
+    }
+    violations.extend(groups.into_iter().filter(|(_, cells)| cells.len() > 1).map(
+        |(group, cells)| RuleViolation {
+            rule: Rule::Exclusion(group.clone()),
+            cells,
+        },
+    ));
+
+    for line in grid_size.lines() {
+        for (i, &a) in line.iter().enumerate() {
+            for &b in &line[i + 1..] {
+                let (Some(Some(goal_a)), Some(Some(goal_b))) = (goals.get(a), goals.get(b)) else {
+                    continue;
+                };
+
+                if let Some(rule) = line_rule(pool, goal_a, goal_b) {
+                    let violation = RuleViolation {
+                        rule,
+                        cells: vec![a, b],
+                    };
+                    if !violations.contains(&violation) {
+                        violations.push(violation);
+                    }
+                }
+            }
+        }
+    }
+
+    violations
+}
@@ -0,0 +1,462 @@
+use std::{fmt, path::Path};
+
+use strum::EnumIter;
+
+use crate::{
+    board::GridSize,
+    goal::{Goal, GoalPool},
+};
+
+/// Separates the categories inside a single cell of a pool table.
+pub const CATEGORY_SEPARATOR: char = ';';
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum Delimiter {
+    #[default]
+    Comma,
+    Tab,
+}
+
+impl Delimiter {
+    pub fn char(self) -> char {
+        match self {
+            Delimiter::Comma => ',',
+            Delimiter::Tab => '\t',
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Delimiter::Comma => "csv",
+            Delimiter::Tab => "tsv",
+        }
+    }
+
+    /// Tabs for `.tsv` and `.tab` files, commas for anything else.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .as_deref()
+        {
+            Some("tsv" | "tab") => Delimiter::Tab,
+            _ => Delimiter::Comma,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum TableLayout {
+    /// One goal per row.
+    #[default]
+    Rows,
+    /// The board as rows and columns of cells.
+    Grid,
+}
+
+/// Which columns of a pool table hold what, counting from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// First row holds column names and is skipped.
+    pub header: bool,
+    pub name: usize,
+    pub difficulty: Option<usize>,
+    pub categories: Option<usize>,
+    pub weight: Option<usize>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        Self {
+            header: true,
+            name: 0,
+            difficulty: Some(1),
+            categories: Some(2),
+            weight: Some(3),
+        }
+    }
+}
+
+/// Spreadsheet name of a column, `A` for 0, `AA` after `Z`.
+pub fn column_name(column: usize) -> String {
+    let mut name = String::new();
+    let mut n = column + 1;
+    while n > 0 {
+        name.insert(0, (b'A' + ((n - 1) % 26) as u8) as char);
+        n = (n - 1) / 26;
+    }
+
+    name
+}
+
+/// Column from a spreadsheet name like `C` or a number counting from 1.
+pub fn parse_column(text: &str) -> Option<usize> {
+    let text = text.trim();
+    if let Ok(number) = text.parse::<usize>() {
+        return number.checked_sub(1);
+    }
+    if text.is_empty() || !text.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    text.to_ascii_uppercase()
+        .bytes()
+        .try_fold(0usize, |n, b| {
+            n.checked_mul(26)?.checked_add((b - b'A' + 1) as usize)
+        })
+        .map(|n| n - 1)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    /// A quoted field runs to the end of the text, `line` is where the quote opened.
+    UnclosedQuote {
+        line: usize,
+    },
+    /// `row` counts from 1.
+    BadNumber {
+        row: usize,
+        column: usize,
+        value: String,
+    },
+    NotSquare {
+        rows: usize,
+        columns: usize,
+    },
+    Empty,
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::UnclosedQuote { line } => {
+                write!(f, "Quote opened on line {line} is never closed")
+            }
+            TableError::BadNumber { row, column, value } => write!(
+                f,
+                "Row {row}, column {}: '{value}' isn't a valid number",
+                column + 1
+            ),
+            TableError::NotSquare { rows, columns } => write!(
+                f,
+                "A board needs as many rows as columns, between 3 and 9, this has {rows} rows and {columns} columns"
+            ),
+            TableError::Empty => write!(f, "Table has no rows"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// Splits delimited text into rows of fields. Quoted fields can hold delimiters, newlines and
+/// doubled quotes. Blank rows at the end are dropped, the ones before can be empty board rows.
+pub fn parse(text: &str, delimiter: Delimiter) -> Result<Vec<Vec<String>>, TableError> {
+    let delimiter = delimiter.char();
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut quote_line = 0;
+    let mut line = 1;
+
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                '\r' if chars.peek() == Some(&'\n') => {}
+                c => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => {
+                quoted = true;
+                quote_line = line;
+            }
+            c if c == delimiter => row.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+
+    if quoted {
+        return Err(TableError::UnclosedQuote { line: quote_line });
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    while rows
+        .last()
+        .is_some_and(|row| row.iter().all(|f| f.trim().is_empty()))
+    {
+        rows.pop();
+    }
+
+    Ok(rows)
+}
+
+/// Joins rows back into delimited text, quoting fields that need it.
+pub fn write(rows: &[Vec<String>], delimiter: Delimiter) -> String {
+    let delimiter = delimiter.char();
+    let mut text = String::new();
+
+    for row in rows {
+        for (idx, field) in row.iter().enumerate() {
+            if idx > 0 {
+                text.push(delimiter);
+            }
+
+            if field.contains([delimiter, '"', '\n', '\r']) {
+                text.push('"');
+                text.push_str(&field.replace('"', "\"\""));
+                text.push('"');
+            } else {
+                text.push_str(field);
+            }
+        }
+        text.push_str("\r\n");
+    }
+
+    text
+}
+
+/// One goal per row, rows without a name are skipped.
+pub fn pool_from_rows(
+    rows: &[Vec<String>],
+    mapping: &ColumnMapping,
+) -> Result<GoalPool, TableError> {
+    let skip = usize::from(mapping.header);
+    let mut goals = Vec::new();
+
+    for (idx, row) in rows.iter().enumerate().skip(skip) {
+        let cell = |column: Option<usize>| {
+            column
+                .and_then(|c| row.get(c))
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+        };
+        let bad_number = |column: usize, value: &str| TableError::BadNumber {
+            row: idx + 1,
+            column,
+            value: value.to_owned(),
+        };
+
+        let Some(name) = cell(Some(mapping.name)) else {
+            continue;
+        };
+
+        let difficulty = match (mapping.difficulty, cell(mapping.difficulty)) {
+            (Some(column), Some(value)) => value.parse().map_err(|_| bad_number(column, value))?,
+            _ => 0,
+        };
+        let weight = match (mapping.weight, cell(mapping.weight)) {
+            (Some(column), Some(value)) => Some(
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|w| w.is_finite() && *w >= 0.0)
+                    .ok_or_else(|| bad_number(column, value))?,
+            ),
+            _ => None,
+        };
+        let categories = cell(mapping.categories)
+            .map(|value| {
+                value
+                    .split(CATEGORY_SEPARATOR)
+                    .map(|c| c.trim().to_owned())
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        goals.push(Goal {
+            name: name.to_owned(),
+            difficulty,
+            categories,
+            weight,
+            ..Default::default()
+        });
+    }
+
+    Ok(GoalPool {
+        goals,
+        ..Default::default()
+    })
+}
+
+/// Rows in the layout of [`ColumnMapping::default`], header included.
+pub fn pool_rows(goals: &[&Goal]) -> Vec<Vec<String>> {
+    let header = ["Goal", "Difficulty", "Categories", "Weight"]
+        .map(String::from)
+        .to_vec();
+
+    std::iter::once(header)
+        .chain(goals.iter().map(|g| {
+            vec![
+                g.name.trim().to_owned(),
+                g.difficulty.to_string(),
+                g.categories.join(&format!("{CATEGORY_SEPARATOR} ")),
+                g.weight.map(|w| w.to_string()).unwrap_or_default(),
+            ]
+        }))
+        .collect()
+}
+
+/// Board cells row-major from a square table, short rows are padded with empty cells.
+pub fn grid_from_rows(rows: &[Vec<String>]) -> Result<(GridSize, Vec<String>), TableError> {
+    if rows.is_empty() {
+        return Err(TableError::Empty);
+    }
+
+    // Trailing empty columns are usually spreadsheet leftovers.
+    let columns = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .rposition(|f| !f.trim().is_empty())
+                .map_or(0, |p| p + 1)
+        })
+        .max()
+        .unwrap_or(0);
+
+    let grid_size = GridSize::from_repr(rows.len())
+        .filter(|_| columns == rows.len())
+        .ok_or(TableError::NotSquare {
+            rows: rows.len(),
+            columns,
+        })?;
+
+    let cells = rows
+        .iter()
+        .flat_map(|row| {
+            (0..columns).map(|idx| {
+                row.get(idx)
+                    .map(|f| f.trim().to_owned())
+                    .unwrap_or_default()
+            })
+        })
+        .collect();
+
+    Ok((grid_size, cells))
+}
+
+pub fn grid_rows(grid_size: GridSize, cells: &[String]) -> Vec<Vec<String>> {
+    cells
+        .chunks(grid_size.side())
+        .map(|row| row.iter().map(|c| c.trim().to_owned()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter()
+            .map(|row| row.iter().map(|f| f.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let text = concat!(
+            "\u{feff}Goal,Difficulty\r\n",
+            "\"Beat A, B\",2\r\n",
+            "\"Say \"\"hi\"\"\",1\n",
+            "\"Two\nlines\",3\r\n",
+            "\"Windows\r\nlines\",4\r\n",
+        );
+
+        assert_eq!(
+            parse(text, Delimiter::Comma).unwrap(),
+            rows(&[
+                &["Goal", "Difficulty"],
+                &["Beat A, B", "2"],
+                &["Say \"hi\"", "1"],
+                &["Two\nlines", "3"],
+                &["Windows\nlines", "4"],
+            ])
+        );
+    }
+
+    #[test]
+    fn reports_unclosed_quote() {
+        assert_eq!(
+            parse("a\tb\n\"c\td\n", Delimiter::Tab),
+            Err(TableError::UnclosedQuote { line: 2 })
+        );
+    }
+
+    #[test]
+    fn writes_what_it_parses() {
+        let table = rows(&[
+            &["Goal", "Notes"],
+            &["Tab\there", "\"quoted\""],
+            &["Line\nbreak", ""],
+        ]);
+
+        for delimiter in [Delimiter::Comma, Delimiter::Tab] {
+            let text = write(&table, delimiter);
+            assert_eq!(parse(&text, delimiter).unwrap(), table);
+        }
+        assert_eq!(
+            write(&table[1..2], Delimiter::Tab),
+            "\"Tab\there\"\t\"\"\"quoted\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn pool_skips_header_and_blank_rows() {
+        let table = parse(
+            "Goal,Difficulty,Categories,Weight\n\nBoss,3,fight; early,2.5\n,,,\nCoins,,,\n",
+            Delimiter::Comma,
+        )
+        .unwrap();
+        let pool = pool_from_rows(&table, &ColumnMapping::default()).unwrap();
+
+        assert_eq!(pool.goals.len(), 2);
+        assert_eq!(pool.goals[0].name, "Boss");
+        assert_eq!(pool.goals[0].difficulty, 3);
+        assert_eq!(pool.goals[0].categories, ["fight", "early"]);
+        assert_eq!(pool.goals[0].weight, Some(2.5));
+        assert_eq!(pool.goals[1].difficulty, 0);
+
+        let bad = parse("Goal,Difficulty\n\nBoss,hard\n", Delimiter::Comma).unwrap();
+        assert_eq!(
+            pool_from_rows(&bad, &ColumnMapping::default()).unwrap_err(),
+            TableError::BadNumber {
+                row: 3,
+                column: 1,
+                value: "hard".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn keeps_blank_board_rows() {
+        let table = parse("a,b,c\n,,\ng,h,i\n\n\n", Delimiter::Comma).unwrap();
+        let (grid_size, cells) = grid_from_rows(&table).unwrap();
+
+        assert_eq!(grid_size, GridSize::Size3);
+        assert_eq!(cells, ["a", "b", "c", "", "", "", "g", "h", "i"]);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::{board::GenerateOptions, card::ExportFormat};
+
+#[derive(Debug, Clone, Default)]
+pub struct PlanOptions {
+    pub rounds: usize,
+    pub boards_per_round: usize,
+    /// Random seed is picked by the backend when empty.
+    pub seed: Option<u64>,
+    /// Seed and pinned goals are ignored.
+    pub generate: GenerateOptions,
+    pub format: ExportFormat,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct BoardReport {
+    pub round: usize,
+    pub board: usize,
+    pub file: String,
+    pub seed: u64,
+    pub total_difficulty: usize,
+    pub line_difficulty_min: usize,
+    pub line_difficulty_max: usize,
+    /// Goals that were already on a board in an earlier round.
+    pub repeated_goals: usize,
+}
+
+/// How the boards of a tournament set compare to each other.
+#[derive(Debug, Clone, Default, serde::Serialize)]
+pub struct FairnessReport {
+    pub boards: Vec<BoardReport>,
+    pub difficulty_min: usize,
+    pub difficulty_max: usize,
+    pub difficulty_mean: f64,
+    pub difficulty_std_dev: f64,
+    pub distinct_goals: usize,
+    pub max_appearances: usize,
+    /// Fewest rounds between two appearances of the same goal, empty when no goal repeats.
+    pub min_round_gap: Option<usize>,
+}
@@ -0,0 +1,17 @@
+use std::path::PathBuf;
+
+use bridge::{
+    board::{GeneratedBoard, GridSize},
+    project::BingoProject,
+};
+
+#[derive(Default)]
+pub struct GeneratedBoardEntity {
+    pub board: Option<GeneratedBoard>,
+    /// Board read from a file instead of generated, cell texts row-major.
+    pub imported: Option<(GridSize, Vec<String>)>,
+    /// Project opened from a `.bingo` file, applied once like `board` and `imported`.
+    pub project: Option<BingoProject>,
+    /// Where the board was last opened from or saved to, saving goes back there.
+    pub project_path: Option<PathBuf>,
+}
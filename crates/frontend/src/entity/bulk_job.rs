@@ -0,0 +1,10 @@
+use bridge::tournament::FairnessReport;
+
+#[derive(Default)]
+pub struct BulkJobEntity {
+    pub running: bool,
+    pub done: usize,
+    pub total: usize,
+    /// Report of the last planned tournament set.
+    pub report: Option<FairnessReport>,
+}
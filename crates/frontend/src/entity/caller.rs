@@ -0,0 +1,11 @@
+use std::path::PathBuf;
+
+use bridge::caller::{CallerGame, CardSet};
+
+#[derive(Default)]
+pub struct CallerEntity {
+    /// Card set file the game is saved next to.
+    pub path: Option<PathBuf>,
+    pub card_set: Option<CardSet>,
+    pub game: Option<CallerGame>,
+}
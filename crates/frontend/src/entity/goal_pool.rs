@@ -0,0 +1,9 @@
+use std::path::PathBuf;
+
+use bridge::goal::GoalPool;
+
+#[derive(Default)]
+pub struct GoalPoolEntity {
+    pub path: Option<PathBuf>,
+    pub pool: Option<GoalPool>,
+}
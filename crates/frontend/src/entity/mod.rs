@@ -0,0 +1,23 @@
+use gpui::{Entity, Global};
+
+use crate::entity::{
+    board::GeneratedBoardEntity, bulk_job::BulkJobEntity, caller::CallerEntity,
+    goal_pool::GoalPoolEntity, usage_history::UsageHistoryEntity,
+};
+
+pub mod board;
+pub mod bulk_job;
+pub mod caller;
+pub mod goal_pool;
+pub mod usage_history;
+
+#[derive(Clone)]
+pub struct DataEntities {
+    pub goal_pool: Entity<GoalPoolEntity>,
+    pub generated_board: Entity<GeneratedBoardEntity>,
+    pub bulk_job: Entity<BulkJobEntity>,
+    pub usage_history: Entity<UsageHistoryEntity>,
+    pub caller: Entity<CallerEntity>,
+}
+
+impl Global for DataEntities {}
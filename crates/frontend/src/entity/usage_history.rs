@@ -0,0 +1,6 @@
+use bridge::history::UsageHistory;
+
+#[derive(Default)]
+pub struct UsageHistoryEntity {
+    pub history: UsageHistory,
+}
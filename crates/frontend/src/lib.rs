@@ -8,6 +8,10 @@ use gpui::{
 use gpui_component::{Root, ThemeMode, TitleBar};
 
 use crate::{
+    entity::{
+        DataEntities, board::GeneratedBoardEntity, bulk_job::BulkJobEntity, caller::CallerEntity,
+        goal_pool::GoalPoolEntity, usage_history::UsageHistoryEntity,
+    },
     processor::Processor,
     root::{AppRoot, AppRootGlobal},
 };
@@ -49,11 +53,25 @@ pub fn start(title: &str, backend_handle: BackendHandle, mut recv: FrontendRecei
         .with_assets(Assets)
         .run(move |cx: &mut App| {
             gpui_component::init(cx);
+            pages::generator_page::init(cx);
             gpui_component::Theme::change(ThemeMode::Dark, None, cx);
 
             let theme = gpui_component::Theme::global_mut(cx);
             theme.scrollbar_show = gpui_component::scroll::ScrollbarShow::Always;
 
+            let goal_pool = cx.new(|_| GoalPoolEntity::default());
+            let generated_board = cx.new(|_| GeneratedBoardEntity::default());
+            let bulk_job = cx.new(|_| BulkJobEntity::default());
+            let usage_history = cx.new(|_| UsageHistoryEntity::default());
+            let caller = cx.new(|_| CallerEntity::default());
+            cx.set_global(DataEntities {
+                goal_pool,
+                generated_board,
+                bulk_job,
+                usage_history,
+                caller,
+            });
+
             let mut window_size = size(px(1600.0), px(1200.0));
 
             if let Some(display) = cx.primary_display() {
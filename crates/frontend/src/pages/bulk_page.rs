@@ -0,0 +1,597 @@
+use bridge::{
+    board::{BulkOptions, DifficultyTarget, GenerateMode, GenerateOptions, GridSize},
+    card::ExportFormat,
+    handle::BackendHandle,
+    message::MessageToBackend,
+    print::{PaperSize, PrintOptions},
+    tournament::PlanOptions,
+};
+use gpui::{
+    AppContext, Context, Entity, IntoElement, ParentElement, Render, SharedString, Styled,
+    Subscription, Window, div, prelude::FluentBuilder,
+};
+use gpui_component::{
+    Disableable, Icon, Selectable, Sizable,
+    button::{Button, ButtonGroup},
+    checkbox::Checkbox,
+    divider::Divider,
+    form::{field, v_form},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    progress::Progress,
+    v_flex,
+};
+use itertools::izip;
+use strum::IntoEnumIterator;
+
+use crate::entity::{DataEntities, bulk_job::BulkJobEntity, goal_pool::GoalPoolEntity};
+
+pub struct BulkPage {
+    backend_handle: BackendHandle,
+    goal_pool: Entity<GoalPoolEntity>,
+    bulk_job: Entity<BulkJobEntity>,
+    count_input: Entity<InputState>,
+    first_seed_input: Entity<InputState>,
+    target_input: Entity<InputState>,
+    tolerance_input: Entity<InputState>,
+    rounds_input: Entity<InputState>,
+    boards_per_round_input: Entity<InputState>,
+    title_input: Entity<InputState>,
+    cards_input: Entity<InputState>,
+    grid_size: GridSize,
+    mode: GenerateMode,
+    format: ExportFormat,
+    unique_goals: bool,
+    paper: PaperSize,
+    cards_per_page: usize,
+    free_center: bool,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl BulkPage {
+    pub fn new(backend_handle: BackendHandle, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let entities = cx.global::<DataEntities>().clone();
+
+        let _subscriptions = vec![
+            cx.observe(&entities.goal_pool, |_, _, cx| cx.notify()),
+            cx.observe(&entities.bulk_job, |_, _, cx| cx.notify()),
+        ];
+
+        Self {
+            backend_handle,
+            goal_pool: entities.goal_pool,
+            bulk_job: entities.bulk_job,
+            count_input: cx.new(|cx| InputState::new(window, cx).default_value("10")),
+            first_seed_input: cx.new(|cx| InputState::new(window, cx).placeholder("Random")),
+            target_input: cx.new(|cx| InputState::new(window, cx).placeholder("Target")),
+            tolerance_input: cx.new(|cx| InputState::new(window, cx).placeholder("Tolerance")),
+            rounds_input: cx.new(|cx| InputState::new(window, cx).placeholder("Rounds")),
+            boards_per_round_input: cx
+                .new(|cx| InputState::new(window, cx).placeholder("Boards per round")),
+            title_input: cx.new(|cx| InputState::new(window, cx).placeholder("Title")),
+            cards_input: cx.new(|cx| InputState::new(window, cx).default_value("20")),
+            grid_size: GridSize::default(),
+            mode: GenerateMode::default(),
+            format: ExportFormat::default(),
+            unique_goals: false,
+            paper: PaperSize::default(),
+            cards_per_page: 1,
+            free_center: true,
+            _subscriptions,
+        }
+    }
+
+    fn start(&self, cx: &mut Context<Self>) {
+        let goal_pool = self.goal_pool.read(cx);
+        let Some(pool) = goal_pool.pool.clone() else {
+            return;
+        };
+        let pool_path = goal_pool.path.clone();
+
+        let count = self
+            .count_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        let first_seed = self.seed(cx);
+        let generate = self.generate_options(cx);
+
+        self.bulk_job.update(cx, |bulk_job, cx| {
+            bulk_job.running = true;
+            bulk_job.done = 0;
+            bulk_job.total = count;
+            cx.notify();
+        });
+
+        self.backend_handle.send(MessageToBackend::BulkGenerate {
+            pool,
+            pool_path,
+            options: BulkOptions {
+                count,
+                first_seed,
+                generate,
+                format: self.format,
+                unique_goals: self.unique_goals,
+            },
+        });
+    }
+
+    fn plan(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        let rounds = self
+            .rounds_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        let boards_per_round = self
+            .boards_per_round_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        let seed = self.seed(cx);
+        let generate = self.generate_options(cx);
+
+        self.bulk_job.update(cx, |bulk_job, cx| {
+            bulk_job.running = true;
+            bulk_job.done = 0;
+            bulk_job.total = rounds * boards_per_round;
+            bulk_job.report = None;
+            cx.notify();
+        });
+
+        self.backend_handle.send(MessageToBackend::PlanTournament {
+            pool,
+            options: PlanOptions {
+                rounds,
+                boards_per_round,
+                seed,
+                generate,
+                format: self.format,
+            },
+        });
+    }
+
+    fn print(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        let count = self
+            .cards_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .unwrap_or(0);
+        let title = self.title_input.read(cx).value().trim().to_string();
+        let first_seed = self.seed(cx);
+        let generate = self.generate_options(cx);
+
+        self.bulk_job.update(cx, |bulk_job, cx| {
+            bulk_job.running = true;
+            bulk_job.done = 0;
+            bulk_job.total = count;
+            cx.notify();
+        });
+
+        self.backend_handle.send(MessageToBackend::CreatePdfCards {
+            pool,
+            options: PrintOptions {
+                count,
+                title,
+                first_seed,
+                generate,
+                paper: self.paper,
+                cards_per_page: self.cards_per_page,
+                free_center: self.free_center,
+            },
+        });
+    }
+
+    fn seed(&self, cx: &Context<Self>) -> Option<u64> {
+        self.first_seed_input.read(cx).value().trim().parse().ok()
+    }
+
+    fn generate_options(&self, cx: &Context<Self>) -> GenerateOptions {
+        let line_difficulty =
+            self.target_input
+                .read(cx)
+                .value()
+                .trim()
+                .parse()
+                .ok()
+                .map(|target| DifficultyTarget {
+                    target,
+                    tolerance: self
+                        .tolerance_input
+                        .read(cx)
+                        .value()
+                        .trim()
+                        .parse()
+                        .unwrap_or(0),
+                });
+
+        GenerateOptions {
+            grid_size: self.grid_size,
+            mode: self.mode,
+            line_difficulty,
+            ..Default::default()
+        }
+    }
+}
+
+impl Render for BulkPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let pool_loaded = self.goal_pool.read(cx).pool.is_some();
+        let bulk_job = self.bulk_job.read(cx);
+        let running = bulk_job.running;
+        let progress = if bulk_job.total > 0 {
+            bulk_job.done as f32 / bulk_job.total as f32 * 100.
+        } else {
+            0.
+        };
+        let progress_label = format!("{} / {} boards", bulk_job.done, bulk_job.total);
+        let report = bulk_job.report.clone();
+
+        v_flex()
+            .gap_4()
+            .p_4()
+            .size_full()
+            .child(
+                v_form()
+                    .layout(gpui::Axis::Horizontal)
+                    .columns(2)
+                    .child(
+                        field().label("Grid Size").child(
+                            div().child(
+                                ButtonGroup::new("bulk-grid-size")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            GridSize::iter(),
+                                            [
+                                                "bulk-size3-btn",
+                                                "bulk-size4-btn",
+                                                "bulk-size5-btn",
+                                                "bulk-size6-btn",
+                                                "bulk-size7-btn",
+                                                "bulk-size8-btn",
+                                                "bulk-size9-btn",
+                                            ],
+                                            ["3x3", "4x4", "5x5", "6x6", "7x7", "8x8", "9x9"],
+                                        )
+                                        .map(
+                                            |(el, name, label)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .selected(self.grid_size == el)
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        if let Some(grid_size) =
+                                            selected.first().and_then(|&v| GridSize::iter().nth(v))
+                                        {
+                                            view.grid_size = grid_size;
+                                        }
+
+                                        cx.notify();
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Format").child(
+                            div().child(
+                                ButtonGroup::new("bulk-format")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            ExportFormat::iter(),
+                                            ["bulk-bingosync-btn", "bulk-lockout-btn"],
+                                            ["Bingosync", "Lockout Live"],
+                                        )
+                                        .map(
+                                            |(format, name, label)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .selected(self.format == format)
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        if let Some(format) = selected
+                                            .first()
+                                            .and_then(|&v| ExportFormat::iter().nth(v))
+                                        {
+                                            view.format = format;
+                                        }
+
+                                        cx.notify();
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().label("Boards").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.count_input).small())
+                                .child(Input::new(&self.first_seed_input).small()),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Mode").child(
+                            div().child(
+                                ButtonGroup::new("bulk-mode")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            GenerateMode::iter(),
+                                            [
+                                                "bulk-random-btn",
+                                                "bulk-balanced-btn",
+                                                "bulk-constrained-btn",
+                                            ],
+                                            ["Random", "Balanced", "Constrained"],
+                                        )
+                                        .map(
+                                            |(mode, name, label)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .selected(self.mode == mode)
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        if let Some(mode) = selected
+                                            .first()
+                                            .and_then(|&v| GenerateMode::iter().nth(v))
+                                        {
+                                            view.mode = mode;
+                                        }
+
+                                        cx.notify();
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().label("Line Difficulty").child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Input::new(&self.target_input)
+                                        .small()
+                                        .disabled(self.mode != GenerateMode::Constrained),
+                                )
+                                .child(
+                                    Input::new(&self.tolerance_input)
+                                        .small()
+                                        .disabled(self.mode != GenerateMode::Constrained),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Options").child(
+                            Checkbox::new("unique-goals-checkbox")
+                                .label("Don't repeat goals across boards")
+                                .checked(self.unique_goals)
+                                .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                    view.unique_goals = *checked;
+                                    cx.notify();
+                                })),
+                        ),
+                    ),
+            )
+            .child(
+                h_flex()
+                    .gap_4()
+                    .child(
+                        Button::new("bulk-generate-btn")
+                            .label("Generate")
+                            .icon(Icon::new(Icon::empty()).path("icons/dices.svg"))
+                            .disabled(!pool_loaded || running)
+                            .on_click(cx.listener(|view, _, _, cx| view.start(cx))),
+                    )
+                    .child(div().flex_1().child(Progress::new().value(progress)))
+                    .child(Label::new(progress_label)),
+            )
+            .child(Divider::horizontal().gap_4())
+            .child(
+                v_form()
+                    .layout(gpui::Axis::Horizontal)
+                    .columns(2)
+                    .child(
+                        field().label("Printable Cards").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.title_input).small())
+                                .child(Input::new(&self.cards_input).small()),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Paper").child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    ButtonGroup::new("print-paper")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                PaperSize::iter(),
+                                                ["print-a4-btn", "print-letter-btn"],
+                                                ["A4", "Letter"],
+                                            )
+                                            .map(|(paper, name, label)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .selected(self.paper == paper)
+                                            }),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                if let Some(paper) = selected
+                                                    .first()
+                                                    .and_then(|&v| PaperSize::iter().nth(v))
+                                                {
+                                                    view.paper = paper;
+                                                }
+
+                                                cx.notify();
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    ButtonGroup::new("print-cards-per-page")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                [1, 2, 4],
+                                                [
+                                                    "print-one-btn",
+                                                    "print-two-btn",
+                                                    "print-four-btn"
+                                                ],
+                                                ["1 per page", "2 per page", "4 per page"],
+                                            )
+                                            .map(|(count, name, label)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .selected(self.cards_per_page == count)
+                                            }),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                if let Some(&count) =
+                                                    selected.first().and_then(|&v| [1, 2, 4].get(v))
+                                                {
+                                                    view.cards_per_page = count;
+                                                }
+
+                                                cx.notify();
+                                            },
+                                        )),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().label("Options").child(
+                            Checkbox::new("free-center-checkbox")
+                                .label("FREE center space")
+                                .checked(self.free_center)
+                                .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                    view.free_center = *checked;
+                                    cx.notify();
+                                })),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Actions").child(
+                            Button::new("print-cards-btn")
+                                .label("Save PDF")
+                                .icon(Icon::new(Icon::empty()).path("icons/save.svg"))
+                                .disabled(!pool_loaded || running)
+                                .on_click(cx.listener(|view, _, _, cx| view.print(cx))),
+                        ),
+                    ),
+            )
+            .child(Divider::horizontal().gap_4())
+            .child(
+                v_form()
+                    .layout(gpui::Axis::Horizontal)
+                    .columns(2)
+                    .child(
+                        field().label("Tournament").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.rounds_input).small())
+                                .child(Input::new(&self.boards_per_round_input).small()),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Actions").child(
+                            Button::new("plan-tournament-btn")
+                                .label("Plan Tournament")
+                                .icon(Icon::new(Icon::empty()).path("icons/dices.svg"))
+                                .disabled(!pool_loaded || running)
+                                .on_click(cx.listener(|view, _, _, cx| view.plan(cx))),
+                        ),
+                    ),
+            )
+            .when_some(report, |this, report| {
+                let summary = format!(
+                    "Board difficulty {}..{}, mean {:.1}, standard deviation {:.2}, {} distinct goals, at most {} appearances{}",
+                    report.difficulty_min,
+                    report.difficulty_max,
+                    report.difficulty_mean,
+                    report.difficulty_std_dev,
+                    report.distinct_goals,
+                    report.max_appearances,
+                    report
+                        .min_round_gap
+                        .map(|gap| format!(", repeats at least {gap} rounds apart"))
+                        .unwrap_or_default()
+                );
+
+                this.child(Label::new(summary)).child(
+                    v_flex()
+                        .gap_1()
+                        .child(report_row(
+                            "Round",
+                            "Board",
+                            "Seed",
+                            "Difficulty",
+                            "Lines",
+                            "Repeated",
+                        ))
+                        .children(report.boards.iter().map(|board| {
+                            report_row(
+                                board.round.to_string(),
+                                board.board.to_string(),
+                                board.seed.to_string(),
+                                board.total_difficulty.to_string(),
+                                format!(
+                                    "{}..{}",
+                                    board.line_difficulty_min, board.line_difficulty_max
+                                ),
+                                board.repeated_goals.to_string(),
+                            )
+                        })),
+                )
+            })
+    }
+}
+
+fn report_row(
+    round: impl Into<SharedString>,
+    board: impl Into<SharedString>,
+    seed: impl Into<SharedString>,
+    difficulty: impl Into<SharedString>,
+    lines: impl Into<SharedString>,
+    repeated: impl Into<SharedString>,
+) -> impl IntoElement {
+    h_flex()
+        .gap_4()
+        .child(div().w_16().child(Label::new(round)))
+        .child(div().w_16().child(Label::new(board)))
+        .child(div().w_48().child(Label::new(seed)))
+        .child(div().w_24().child(Label::new(difficulty)))
+        .child(div().w_24().child(Label::new(lines)))
+        .child(div().w_24().child(Label::new(repeated)))
+}
@@ -0,0 +1,271 @@
+use bridge::{handle::BackendHandle, message::MessageToBackend};
+use gpui::{
+    AppContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Subscription, Window,
+    div, prelude::FluentBuilder,
+};
+use gpui_component::{
+    Disableable, Icon, Sizable,
+    button::{Button, ButtonGroup, ButtonVariants},
+    divider::Divider,
+    form::{field, v_form},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    v_flex,
+};
+use itertools::izip;
+
+use crate::{
+    entity::{DataEntities, caller::CallerEntity},
+    pages::prompt_single_file,
+};
+
+pub struct CallerPage {
+    backend_handle: BackendHandle,
+    caller: Entity<CallerEntity>,
+    seed_input: Entity<InputState>,
+    card_number_input: Entity<InputState>,
+    /// Outcome of the last card check.
+    check_result: Option<String>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl CallerPage {
+    pub fn new(backend_handle: BackendHandle, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let caller = cx.global::<DataEntities>().caller.clone();
+
+        let _subscriptions = vec![cx.observe(&caller, |_, _, cx| cx.notify())];
+
+        Self {
+            backend_handle,
+            caller,
+            seed_input: cx.new(|cx| InputState::new(window, cx).placeholder("Random")),
+            card_number_input: cx.new(|cx| InputState::new(window, cx).placeholder("Card")),
+            check_result: None,
+            _subscriptions,
+        }
+    }
+
+    fn open_card_set(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Open Card Set", |path| {
+            MessageToBackend::OpenCardSet { path }
+        });
+    }
+
+    fn restart(&self, cx: &mut Context<Self>) {
+        let Some(path) = self.caller.read(cx).path.clone() else {
+            return;
+        };
+
+        self.backend_handle
+            .send(MessageToBackend::RestartCallerGame {
+                path,
+                seed: self.seed_input.read(cx).value().trim().parse().ok(),
+            });
+    }
+
+    fn caller_sheet(&self, cx: &mut Context<Self>) {
+        let Some(path) = self.caller.read(cx).path.clone() else {
+            return;
+        };
+
+        self.backend_handle
+            .send(MessageToBackend::CreateCallerSheet { path });
+    }
+
+    /// Calls the next goal, or takes back the last call when `step` is -1, and saves the game.
+    fn call(&mut self, step: isize, cx: &mut Context<Self>) {
+        let saved = self.caller.update(cx, |caller, cx| {
+            let path = caller.path.clone()?;
+            let game = caller.game.as_mut()?;
+            game.called = game
+                .called
+                .saturating_add_signed(step)
+                .min(game.order.len());
+            cx.notify();
+
+            Some((path, game.clone()))
+        });
+
+        if let Some((path, game)) = saved {
+            self.backend_handle
+                .send(MessageToBackend::SaveCallerGame { path, game });
+        }
+        self.check_result = None;
+    }
+
+    fn check_card(&mut self, cx: &mut Context<Self>) {
+        let caller = self.caller.read(cx);
+        let (Some(card_set), Some(game)) = (&caller.card_set, &caller.game) else {
+            return;
+        };
+        let number = self.card_number_input.read(cx).value().trim().parse().ok();
+
+        self.check_result = Some(match number.map(|n| (n, card_set.check(n, game))) {
+            None => "Enter a card number".to_string(),
+            Some((number, None)) => format!(
+                "There's no card {}, the set has {}",
+                number,
+                card_set.cards.len()
+            ),
+            Some((number, Some(status))) if status.blackout => {
+                format!("Card {}: BLACKOUT", number)
+            }
+            Some((number, Some(status))) if !status.lines.is_empty() => {
+                format!("Card {}: BINGO on {}", number, status.lines.join(", "))
+            }
+            Some((number, Some(status))) => format!(
+                "Card {}: no line yet, {} cells marked",
+                number, status.marked
+            ),
+        });
+        cx.notify();
+    }
+}
+
+impl Render for CallerPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let caller = self.caller.read(cx);
+        let loaded = caller.game.is_some();
+
+        let path_label = caller
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "No card set loaded".to_string());
+        let (called, finished, progress_label, last_called) = match &caller.game {
+            Some(game) => (
+                game.called().to_vec(),
+                game.finished(),
+                format!(
+                    "{} of {} goals called, seed {}",
+                    game.called().len(),
+                    game.order.len(),
+                    game.seed
+                ),
+                game.last_called().cloned(),
+            ),
+            None => (Vec::new(), true, String::new(), None),
+        };
+
+        v_flex()
+            .gap_4()
+            .p_4()
+            .size_full()
+            .child(
+                v_form()
+                    .layout(gpui::Axis::Horizontal)
+                    .columns(2)
+                    .child(field().label("Card Set").child(Label::new(path_label)))
+                    .child(
+                        field().col_start(2).label("Actions").child(
+                            div().child(
+                                ButtonGroup::new("caller-actions")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            ["open-card-set-btn", "caller-sheet-btn"],
+                                            ["Open", "Caller Sheet"],
+                                            [None, Some("icons/save.svg")],
+                                            [false, !loaded],
+                                        )
+                                        .map(
+                                            |(name, label, icon, disabled)| {
+                                                let btn = Button::new(name)
+                                                    .label(label)
+                                                    .disabled(disabled);
+
+                                                if let Some(i) = icon {
+                                                    return btn
+                                                        .icon(Icon::new(Icon::empty()).path(i));
+                                                }
+
+                                                btn
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        match selected.first() {
+                                            Some(0) => view.open_card_set(cx),
+                                            Some(1) => view.caller_sheet(cx),
+                                            _ => {}
+                                        }
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().label("New Game").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.seed_input).small())
+                                .child(
+                                    Button::new("restart-game-btn")
+                                        .outline()
+                                        .compact()
+                                        .label("Restart")
+                                        .icon(Icon::new(Icon::empty()).path("icons/dices.svg"))
+                                        .disabled(!loaded)
+                                        .on_click(cx.listener(|view, _, _, cx| view.restart(cx))),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Check Card").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.card_number_input).small())
+                                .child(
+                                    Button::new("check-card-btn")
+                                        .outline()
+                                        .compact()
+                                        .label("Check")
+                                        .icon(
+                                            Icon::new(Icon::empty()).path("icons/circle-check.svg"),
+                                        )
+                                        .disabled(!loaded)
+                                        .on_click(
+                                            cx.listener(|view, _, _, cx| view.check_card(cx)),
+                                        ),
+                                ),
+                        ),
+                    ),
+            )
+            .when_some(self.check_result.clone(), |this, result| {
+                this.child(Label::new(result))
+            })
+            .child(Divider::horizontal().gap_4())
+            .child(
+                h_flex()
+                    .gap_4()
+                    .child(
+                        Button::new("call-next-btn")
+                            .label("Call Next")
+                            .disabled(finished)
+                            .on_click(cx.listener(|view, _, _, cx| view.call(1, cx))),
+                    )
+                    .child(
+                        Button::new("undo-call-btn")
+                            .outline()
+                            .label("Undo")
+                            .disabled(called.is_empty())
+                            .on_click(cx.listener(|view, _, _, cx| view.call(-1, cx))),
+                    )
+                    .child(Label::new(progress_label)),
+            )
+            .when_some(last_called, |this, goal| {
+                this.child(div().text_2xl().child(Label::new(goal)))
+            })
+            .child(
+                v_flex()
+                    .gap_1()
+                    .children(called.iter().enumerate().rev().map(|(idx, goal)| {
+                        h_flex()
+                            .gap_4()
+                            .child(div().w_8().child(Label::new((idx + 1).to_string())))
+                            .child(Label::new(goal.clone()))
+                    })),
+            )
+    }
+}
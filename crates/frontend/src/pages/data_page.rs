@@ -1,29 +1,423 @@
-use gpui::{Context, IntoElement, ParentElement, Render, Styled, Window, div};
-use gpui_component::{h_flex, label::Label, v_flex};
+use std::collections::HashMap;
 
-pub struct DataPage {}
+use bridge::{
+    goal::GoalFilter,
+    handle::BackendHandle,
+    message::MessageToBackend,
+    table::{self, ColumnMapping, Delimiter},
+};
+use gpui::{
+    AppContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Subscription, Window,
+    div,
+};
+use gpui_component::{
+    Disableable, Icon, Sizable,
+    button::{Button, ButtonGroup},
+    checkbox::Checkbox,
+    divider::Divider,
+    form::{field, v_form},
+    h_flex,
+    input::{Input, InputState},
+    label::Label,
+    v_flex,
+};
+use itertools::izip;
+
+use crate::{
+    entity::{DataEntities, goal_pool::GoalPoolEntity, usage_history::UsageHistoryEntity},
+    pages::prompt_single_file,
+};
+
+pub struct DataPage {
+    backend_handle: BackendHandle,
+    goal_pool: Entity<GoalPoolEntity>,
+    usage_history: Entity<UsageHistoryEntity>,
+    /// Exports per goal over the whole usage history, by pool name.
+    uses: HashMap<String, usize>,
+    category_input: Entity<InputState>,
+    min_difficulty_input: Entity<InputState>,
+    max_difficulty_input: Entity<InputState>,
+    /// Spreadsheet columns of the goal, difficulty, categories and weight when importing.
+    column_inputs: [Entity<InputState>; 4],
+    header_row: bool,
+    /// Seed for expanding templates in the Bingosync pool export.
+    seed_input: Entity<InputState>,
+    _subscriptions: Vec<Subscription>,
+}
 
 impl DataPage {
-    pub fn new(mut window: &mut Window, cx: &mut Context<Self>) -> Self {
-        Self {}
+    pub fn new(backend_handle: BackendHandle, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let entities = cx.global::<DataEntities>().clone();
+        let goal_pool = entities.goal_pool;
+        let usage_history = entities.usage_history;
+
+        let category_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Categories, comma separated"));
+        let min_difficulty_input = cx.new(|cx| InputState::new(window, cx).placeholder("Min"));
+        let max_difficulty_input = cx.new(|cx| InputState::new(window, cx).placeholder("Max"));
+        let mapping = ColumnMapping::default();
+        let column_inputs = [
+            Some(mapping.name),
+            mapping.difficulty,
+            mapping.categories,
+            mapping.weight,
+        ]
+        .map(|column| {
+            let value = column.map(table::column_name).unwrap_or_default();
+            cx.new(|cx| {
+                InputState::new(window, cx)
+                    .placeholder("None")
+                    .default_value(value)
+            })
+        });
+
+        let _subscriptions = vec![
+            cx.observe(&goal_pool, |view, _, cx| view.count_uses(cx)),
+            cx.observe(&usage_history, |view, _, cx| view.count_uses(cx)),
+            cx.observe(&category_input, |_, _, cx| cx.notify()),
+            cx.observe(&min_difficulty_input, |_, _, cx| cx.notify()),
+            cx.observe(&max_difficulty_input, |_, _, cx| cx.notify()),
+        ];
+
+        let mut page = Self {
+            backend_handle,
+            goal_pool,
+            usage_history,
+            uses: HashMap::new(),
+            category_input,
+            min_difficulty_input,
+            max_difficulty_input,
+            column_inputs,
+            header_row: mapping.header,
+            seed_input: cx.new(|cx| InputState::new(window, cx).placeholder("Random")),
+            _subscriptions,
+        };
+        page.count_uses(cx);
+
+        page
+    }
+
+    fn count_uses(&mut self, cx: &mut Context<Self>) {
+        self.uses = self
+            .goal_pool
+            .read(cx)
+            .pool
+            .as_ref()
+            .map(|pool| self.usage_history.read(cx).history.uses(pool, None))
+            .unwrap_or_default();
+        cx.notify();
+    }
+
+    fn filter(&self, cx: &Context<Self>) -> GoalFilter {
+        GoalFilter {
+            categories: self
+                .category_input
+                .read(cx)
+                .value()
+                .split(',')
+                .map(|c| c.trim().to_string())
+                .filter(|c| !c.is_empty())
+                .collect(),
+            min_difficulty: self
+                .min_difficulty_input
+                .read(cx)
+                .value()
+                .trim()
+                .parse()
+                .ok(),
+            max_difficulty: self
+                .max_difficulty_input
+                .read(cx)
+                .value()
+                .trim()
+                .parse()
+                .ok(),
+        }
+    }
+
+    fn open_pool(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Open Goal Pool", |path| {
+            MessageToBackend::LoadGoalPool { path }
+        });
+    }
+
+    fn import_goal_list(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Import Goal List", |path| {
+            MessageToBackend::ImportGoalList { path }
+        });
+    }
+
+    fn column_mapping(&self, cx: &Context<Self>) -> ColumnMapping {
+        let [name, difficulty, categories, weight] = self
+            .column_inputs
+            .each_ref()
+            .map(|input| table::parse_column(&input.read(cx).value()));
+
+        ColumnMapping {
+            header: self.header_row,
+            name: name.unwrap_or(0),
+            difficulty,
+            categories,
+            weight,
+        }
+    }
+
+    fn import_pool_table(&self, cx: &mut Context<Self>) {
+        let mapping = self.column_mapping(cx);
+        prompt_single_file(cx, &self.backend_handle, "Import Goal Pool", move |path| {
+            MessageToBackend::ImportPoolTable { path, mapping }
+        });
+    }
+
+    fn save_pool_table(&self, delimiter: Delimiter, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        self.backend_handle
+            .send(MessageToBackend::CreatePoolTableFile {
+                pool,
+                filter: self.filter(cx),
+                delimiter,
+            });
+    }
+
+    fn save_bingosync_pool(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        self.backend_handle
+            .send(MessageToBackend::CreateBingoSyncPoolFile {
+                pool,
+                filter: self.filter(cx),
+                seed: self.seed_input.read(cx).value().trim().parse().ok(),
+            });
+    }
+
+    fn save_lockout_pool(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        self.backend_handle
+            .send(MessageToBackend::CreateLockoutLivePoolFile {
+                pool,
+                filter: self.filter(cx),
+            });
     }
 }
 
 impl Render for DataPage {
-    fn render(
-        &mut self,
-        window: &mut gpui::Window,
-        cx: &mut gpui::Context<Self>,
-    ) -> impl IntoElement {
-        v_flex().size_full().child(
-            h_flex()
-                .flex_wrap()
-                .justify_center()
-                .items_center()
-                .size_full()
-                .gap_4()
-                .text_xl()
-                .child(div().child(Label::new("Data Page"))),
-        )
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let filter = self.filter(cx);
+        let goal_pool = self.goal_pool.read(cx);
+        let pool_loaded = goal_pool.pool.is_some();
+        let history_boards = self.usage_history.read(cx).history.boards.len();
+
+        let path_label = goal_pool
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| {
+                if pool_loaded {
+                    "Imported goal pool".to_string()
+                } else {
+                    "No goal pool loaded".to_string()
+                }
+            });
+
+        let goals: Vec<_> = goal_pool
+            .pool
+            .iter()
+            .flat_map(|p| p.filtered(&filter))
+            .map(|g| {
+                h_flex()
+                    .gap_4()
+                    .child(div().w_8().child(Label::new(g.difficulty.to_string())))
+                    .child(div().flex_1().child(Label::new(g.name.clone())))
+                    .child(Label::new(g.categories.join(", ")))
+                    .child(div().w_24().child(Label::new(format!(
+                        "{} uses",
+                        self.uses.get(g.name.trim()).copied().unwrap_or(0)
+                    ))))
+            })
+            .collect();
+
+        v_flex()
+            .gap_4()
+            .p_4()
+            .size_full()
+            .child(
+                v_form()
+                    .layout(gpui::Axis::Horizontal)
+                    .columns(2)
+                    .child(field().label("Goal Pool").child(Label::new(path_label)))
+                    .child(
+                        field().col_start(2).label("Actions").child(
+                            div().child(
+                                ButtonGroup::new("pool-actions")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            [
+                                                "open-pool-btn",
+                                                "import-goal-list-btn",
+                                                "save-bingosync-pool-btn",
+                                                "save-lockout-pool-btn",
+                                            ],
+                                            [
+                                                "Open",
+                                                "Text List",
+                                                "Bingosync Pool",
+                                                "Lockout Live Pool"
+                                            ],
+                                            [
+                                                None,
+                                                None,
+                                                Some(
+                                                    Icon::new(Icon::empty()).path("icons/save.svg")
+                                                ),
+                                                Some(
+                                                    Icon::new(Icon::empty()).path("icons/save.svg")
+                                                ),
+                                            ],
+                                            [false, false, !pool_loaded, !pool_loaded],
+                                        )
+                                        .map(
+                                            |(name, label, icon, disabled)| {
+                                                let btn = Button::new(name)
+                                                    .label(label)
+                                                    .disabled(disabled);
+
+                                                if let Some(i) = icon {
+                                                    return btn.icon(i);
+                                                }
+
+                                                btn
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(
+                                        |view, selected: &Vec<usize>, _w, cx| match selected.first()
+                                        {
+                                            Some(0) => view.open_pool(cx),
+                                            Some(1) => view.import_goal_list(cx),
+                                            Some(2) => view.save_bingosync_pool(cx),
+                                            Some(3) => view.save_lockout_pool(cx),
+                                            _ => {}
+                                        },
+                                    )),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field()
+                            .label("Categories")
+                            .child(Input::new(&self.category_input).small()),
+                    )
+                    .child(
+                        field().col_start(2).label("Difficulty").child(
+                            h_flex()
+                                .gap_2()
+                                .child(Input::new(&self.min_difficulty_input).small())
+                                .child(Input::new(&self.max_difficulty_input).small()),
+                        ),
+                    )
+                    .child(
+                        field().label("Spreadsheet").child(
+                            h_flex()
+                                .gap_4()
+                                .child(
+                                    ButtonGroup::new("pool-table")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                [
+                                                    "import-pool-table-btn",
+                                                    "save-pool-csv-btn",
+                                                    "save-pool-tsv-btn",
+                                                ],
+                                                ["Import", "CSV", "TSV"],
+                                                [
+                                                    None,
+                                                    Some("icons/save.svg"),
+                                                    Some("icons/save.svg")
+                                                ],
+                                                [false, !pool_loaded, !pool_loaded],
+                                            )
+                                            .map(
+                                                |(name, label, icon, disabled)| {
+                                                    let btn = Button::new(name)
+                                                        .label(label)
+                                                        .disabled(disabled);
+
+                                                    if let Some(i) = icon {
+                                                        return btn.icon(
+                                                            Icon::new(Icon::empty()).path(i),
+                                                        );
+                                                    }
+
+                                                    btn
+                                                },
+                                            ),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                match selected.first() {
+                                                    Some(0) => view.import_pool_table(cx),
+                                                    Some(1) => {
+                                                        view.save_pool_table(Delimiter::Comma, cx)
+                                                    }
+                                                    Some(2) => {
+                                                        view.save_pool_table(Delimiter::Tab, cx)
+                                                    }
+                                                    _ => {}
+                                                }
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    Checkbox::new("header-row-checkbox")
+                                        .label("Header row")
+                                        .checked(self.header_row)
+                                        .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                            view.header_row = *checked;
+                                            cx.notify();
+                                        })),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Columns").child(
+                            h_flex().gap_2().children(
+                                izip!(
+                                    ["Goal", "Difficulty", "Categories", "Weight"],
+                                    &self.column_inputs
+                                )
+                                .map(|(label, input)| {
+                                    h_flex()
+                                        .gap_1()
+                                        .child(Label::new(label))
+                                        .child(Input::new(input).small())
+                                }),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field()
+                            .label("Template Seed")
+                            .child(Input::new(&self.seed_input).small()),
+                    ),
+            )
+            .child(Divider::horizontal().gap_4())
+            .child(Label::new(format!(
+                "{} goals, {} boards in usage history",
+                goals.len(),
+                history_boards
+            )))
+            .child(v_flex().gap_1().children(goals))
     }
 }
@@ -1,52 +1,355 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bridge::{
-    card::{BingoSyncCard, LockoutLiveBoard, LockoutLiveCard},
+    board::{
+        BoardConstraints, DifficultyTarget, GenerateMode, GenerateOptions, GeneratedBoard,
+        GridSize, LineSpread,
+    },
+    card::ExportFormat,
+    code::{self, BoardCode},
+    goal::{Goal, GoalPool},
     handle::BackendHandle,
+    history::{AvoidMode, AvoidRecent, HistoryWindow},
+    image::{BoardImage, ImageCell, ImageFormat},
     message::MessageToBackend,
+    metadata::ExportMetadata,
+    project::{BingoProject, LockoutLimits, ProjectCell},
+    rules,
+    table::{self, Delimiter},
 };
 use gpui::{
-    App, AppContext, Context, Entity, FocusHandle, Focusable, IntoElement, ParentElement, Render,
-    Styled, Window, div, prelude::FluentBuilder, px,
+    App, AppContext, ClipboardItem, Context, Entity, FocusHandle, Focusable, InteractiveElement,
+    IntoElement, KeyBinding, MouseButton, MouseDownEvent, ParentElement, Render, SharedString,
+    Styled, Subscription, Window, actions, div, prelude::FluentBuilder, px, transparent_black,
 };
 use gpui_component::{
-    Disableable, Icon, Selectable, Sizable, WindowExt,
-    button::{Button, ButtonGroup},
+    Disableable, Icon, Selectable, Sizable, WindowExt, blue_600,
+    button::{Button, ButtonGroup, ButtonVariants},
+    checkbox::Checkbox,
     divider::Divider,
     form::{field, v_form},
-    input::{Input, InputState},
+    green_600, h_flex,
+    input::{Input, InputState, Paste},
+    label::Label,
+    menu::ContextMenuExt,
     notification::NotificationType,
-    red_600, red_800, v_flex,
+    purple_500, red_600, red_800, v_flex, yellow_600,
 };
 use itertools::izip;
 use log::info;
-use strum::{EnumIter, FromRepr, IntoEnumIterator};
-
-#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, EnumIter, FromRepr)]
-#[repr(usize)]
-enum GridSize {
-    Size3 = 3,
-    Size4,
-    #[default]
-    Size5,
-    Size6,
-    Size7,
-    Size8,
-    Size9,
+use rand::seq::SliceRandom;
+use strum::IntoEnumIterator;
+
+use crate::{
+    entity::{
+        DataEntities, board::GeneratedBoardEntity, goal_pool::GoalPoolEntity,
+        usage_history::UsageHistoryEntity,
+    },
+    pages::prompt_single_file,
+};
+
+actions!(
+    generator_page,
+    [
+        RerollCell,
+        RerollRow,
+        RerollColumn,
+        RerollDiagonal,
+        RerollAntiDiagonal,
+        MoveUp,
+        MoveDown,
+        MoveLeft,
+        MoveRight,
+        MoveToTop,
+        MoveToBottom,
+        MoveToLeftEdge,
+        MoveToRightEdge,
+        SelectUp,
+        SelectDown,
+        SelectLeft,
+        SelectRight,
+        SelectToTop,
+        SelectToBottom,
+        SelectToLeftEdge,
+        SelectToRightEdge,
+        EditCell,
+        StopEditing,
+        ClearSelection,
+        CopySelection,
+        CutSelection,
+        RerollSelection,
+        SetSelectionCategory
+    ]
+);
+
+const GRID_CONTEXT: &str = "BoardGrid";
+/// Focused while moving around the board. It holds none of the cell inputs, so plain keys bound
+/// here don't fire while typing in a cell.
+const NAVIGATION_CONTEXT: &str = "BoardNavigation";
+
+/// Default keys for moving around the board, `cx.bind_keys` again to change them.
+pub fn init(cx: &mut App) {
+    let context = Some(NAVIGATION_CONTEXT);
+    let editing = Some("BoardGrid > Input");
+
+    cx.bind_keys([
+        KeyBinding::new("up", MoveUp, context),
+        KeyBinding::new("down", MoveDown, context),
+        KeyBinding::new("left", MoveLeft, context),
+        KeyBinding::new("right", MoveRight, context),
+        KeyBinding::new("ctrl-up", MoveToTop, context),
+        KeyBinding::new("ctrl-down", MoveToBottom, context),
+        KeyBinding::new("ctrl-left", MoveToLeftEdge, context),
+        KeyBinding::new("ctrl-right", MoveToRightEdge, context),
+        KeyBinding::new("shift-up", SelectUp, context),
+        KeyBinding::new("shift-down", SelectDown, context),
+        KeyBinding::new("shift-left", SelectLeft, context),
+        KeyBinding::new("shift-right", SelectRight, context),
+        KeyBinding::new("ctrl-shift-up", SelectToTop, context),
+        KeyBinding::new("ctrl-shift-down", SelectToBottom, context),
+        KeyBinding::new("ctrl-shift-left", SelectToLeftEdge, context),
+        KeyBinding::new("ctrl-shift-right", SelectToRightEdge, context),
+        KeyBinding::new("enter", EditCell, context),
+        KeyBinding::new("escape", StopEditing, editing),
+        KeyBinding::new("delete", ClearSelection, context),
+        KeyBinding::new("backspace", ClearSelection, context),
+        KeyBinding::new("ctrl-c", CopySelection, context),
+        KeyBinding::new("ctrl-x", CutSelection, context),
+        KeyBinding::new("r", RerollSelection, context),
+        KeyBinding::new("ctrl-t", SetSelectionCategory, context),
+    ]);
+}
+
+#[derive(Clone, Copy)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Rectangle of board cells between the anchor and the cursor, both `(row, column)`.
+#[derive(Clone, Copy)]
+struct CellSelection {
+    anchor: (usize, usize),
+    cursor: (usize, usize),
+}
+
+impl CellSelection {
+    fn at(cell: (usize, usize)) -> Self {
+        Self {
+            anchor: cell,
+            cursor: cell,
+        }
+    }
+
+    /// Row-major board indices inside the rectangle.
+    fn cells(&self, grid_size: GridSize) -> Vec<usize> {
+        let n = grid_size.side();
+        let (top, bottom) = min_max(self.anchor.0, self.cursor.0);
+        let (left, right) = min_max(self.anchor.1, self.cursor.1);
+
+        (top..=bottom.min(n - 1))
+            .flat_map(|y| (left..=right.min(n - 1)).map(move |x| y * n + x))
+            .collect()
+    }
+
+    /// Rows of the rectangle on the board, clamped like [`CellSelection::cells`].
+    fn rows(&self, grid_size: GridSize) -> usize {
+        let (top, bottom) = min_max(self.anchor.0, self.cursor.0);
+
+        (bottom.min(grid_size.side() - 1) + 1).saturating_sub(top)
+    }
+}
+
+fn min_max(a: usize, b: usize) -> (usize, usize) {
+    (a.min(b), a.max(b))
+}
+
+/// Rearranges the whole board. Every row, column and diagonal keeps its goals, only their order
+/// and where the line sits change.
+#[derive(Clone, Copy)]
+enum BoardTransform {
+    RotateLeft,
+    RotateRight,
+    /// Left to right.
+    Mirror,
+    /// Top to bottom.
+    Flip,
+    Transpose,
+    /// Same random order for rows and columns, mirrored around the middle so the diagonals stay
+    /// diagonals.
+    Shuffle,
+}
+
+impl BoardTransform {
+    /// Row-major board index each cell moves to, by its current index.
+    fn positions(self, grid_size: GridSize) -> Vec<usize> {
+        let n = grid_size.side();
+        let order = match self {
+            BoardTransform::Shuffle => line_order(n),
+            _ => (0..n).collect(),
+        };
+
+        (0..n * n)
+            .map(|idx| {
+                let (y, x) = (idx / n, idx % n);
+                let (y, x) = match self {
+                    BoardTransform::RotateLeft => (n - 1 - x, y),
+                    BoardTransform::RotateRight => (x, n - 1 - y),
+                    BoardTransform::Mirror => (y, n - 1 - x),
+                    BoardTransform::Flip => (n - 1 - y, x),
+                    BoardTransform::Transpose => (x, y),
+                    BoardTransform::Shuffle => (order[y], order[x]),
+                };
+
+                y * n + x
+            })
+            .collect()
+    }
+}
+
+/// Random order of `0..n` where lines the same distance from either edge stay that way, the
+/// middle line of odd boards stays put.
+fn line_order(n: usize) -> Vec<usize> {
+    let mut rng = rand::rng();
+    let mut pairs: Vec<usize> = (0..n / 2).collect();
+    pairs.shuffle(&mut rng);
+
+    let mut order: Vec<usize> = (0..n).collect();
+    for (k, pair) in pairs.into_iter().enumerate() {
+        let to = if rand::random() { pair } else { n - 1 - pair };
+        order[k] = to;
+        order[n - 1 - k] = n - 1 - to;
+    }
+
+    order
+}
+
+/// Cell being dragged onto another one to swap them.
+#[derive(Clone)]
+struct DraggedCell {
+    /// Index into `cell_inputs`.
+    idx: usize,
+    text: SharedString,
+}
+
+impl Render for DraggedCell {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(purple_500())
+            .child(Label::new(self.text.clone()))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RerollScope {
+    Cell,
+    Row,
+    Column,
+    Diagonal,
+    AntiDiagonal,
+}
+
+/// Block of clipboard text waiting to be spread over the board.
+struct CellPaste {
+    /// Row-major board index of the cell it was pasted into.
+    origin: usize,
+    rows: Vec<Vec<String>>,
+}
+
+impl CellPaste {
+    fn size(&self) -> (usize, usize) {
+        (
+            self.rows.len(),
+            self.rows.iter().map(Vec::len).max().unwrap_or(0),
+        )
+    }
+
+    /// Smallest grid the whole block fits in from the top left cell.
+    fn grid_size(&self) -> Option<GridSize> {
+        let (rows, columns) = self.size();
+        GridSize::iter().find(|g| g.side() >= rows.max(columns))
+    }
+
+    /// Board indices the values land on from `origin`, values past the edge are left out.
+    fn targets(&self, grid_size: GridSize, origin: usize) -> Vec<(usize, String)> {
+        let n = grid_size.side();
+        let (y, x) = (origin / n, origin % n);
+
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(dy, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(dx, value)| (y + dy, x + dx, value))
+            })
+            .filter(|&(y, x, _)| y < n && x < n)
+            .map(|(y, x, value)| (y * n + x, value.clone()))
+            .collect()
+    }
+
+    fn value_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
 }
 
 enum PageAction {
     Clear,
     SaveBingosync,
     SaveLockout,
+    /// Same JSON as the file export, on the clipboard.
+    CopyJson(ExportFormat),
+    SaveImage(ImageFormat),
+    SaveHtml,
+    ApplyGenerated,
+    CopyCode,
+    PasteCode,
 }
 
 pub struct GeneratorPage {
     focus_handle: FocusHandle,
     backend_handle: BackendHandle,
+    goal_pool: Entity<GoalPoolEntity>,
+    generated_board: Entity<GeneratedBoardEntity>,
+    usage_history: Entity<UsageHistoryEntity>,
     cell_inputs: [Entity<InputState>; 9 * 9],
+    seed_input: Entity<InputState>,
+    target_input: Entity<InputState>,
+    tolerance_input: Entity<InputState>,
+    recent_input: Entity<InputState>,
+    notes_input: Entity<InputState>,
     selected_grid_size: GridSize,
+    mode: GenerateMode,
+    /// How goals from recently exported boards are avoided, not at all when empty.
+    avoid_mode: Option<AvoidMode>,
+    /// `recent_input` counts days instead of boards.
+    recent_in_days: bool,
+    /// Indices into `cell_inputs` kept as they are on randomize.
+    pinned_cells: HashSet<usize>,
+    /// Index into `cell_inputs` the context menu was last opened on.
+    context_cell: Option<usize>,
+    /// Multi-cell paste shown as a preview until it's applied or dismissed.
+    cell_paste: Option<CellPaste>,
+    /// Focused while moving around the board with the keyboard, the inputs while editing.
+    grid_focus: FocusHandle,
+    selection: Option<CellSelection>,
+    category_input: Entity<InputState>,
+    /// Categories set by hand, by index into `cell_inputs`. They go first on the cell's goal.
+    cell_categories: HashMap<usize, String>,
+    line_spread: Option<LineSpread>,
+    /// Write an `.meta.json` next to board exports.
+    write_sidecar: bool,
+    /// Color image cells by the first category of their goal.
+    category_colors: bool,
+    /// HTML board stays covered until clicked.
+    html_spoiler: bool,
     page_action: Option<PageAction>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl GeneratorPage {
@@ -55,18 +358,971 @@ impl GeneratorPage {
         mut window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Self {
+        let entities = cx.global::<DataEntities>().clone();
+
+        let _subscriptions = vec![
+            cx.observe(&entities.goal_pool, |_, _, cx| cx.notify()),
+            cx.observe(&entities.generated_board, |view, _, cx| {
+                view.page_action = Some(PageAction::ApplyGenerated);
+                cx.notify();
+            }),
+        ];
+
         Self {
             focus_handle: cx.focus_handle(),
             backend_handle,
+            goal_pool: entities.goal_pool,
+            generated_board: entities.generated_board,
+            usage_history: entities.usage_history,
             cell_inputs: core::array::from_fn(|_idx| {
                 cx.new(|cx| InputState::new(window, cx).auto_grow(2, 2))
             }),
+            seed_input: cx.new(|cx| InputState::new(window, cx).placeholder("Random")),
+            target_input: cx.new(|cx| InputState::new(window, cx).placeholder("Target")),
+            tolerance_input: cx.new(|cx| InputState::new(window, cx).placeholder("Tolerance")),
+            recent_input: cx.new(|cx| InputState::new(window, cx).placeholder("All")),
+            notes_input: cx.new(|cx| InputState::new(window, cx).placeholder("Project notes")),
             selected_grid_size: Default::default(),
-            page_action: None,
+            mode: GenerateMode::default(),
+            avoid_mode: None,
+            recent_in_days: false,
+            pinned_cells: HashSet::new(),
+            context_cell: None,
+            cell_paste: None,
+            grid_focus: cx.focus_handle(),
+            selection: None,
+            category_input: cx.new(|cx| InputState::new(window, cx).placeholder("Category")),
+            cell_categories: HashMap::new(),
+            line_spread: None,
+            write_sidecar: true,
+            category_colors: true,
+            html_spoiler: false,
+            // Shows the last generated or imported board again after switching tabs.
+            page_action: Some(PageAction::ApplyGenerated),
+            _subscriptions,
+        }
+    }
+
+    fn randomize(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        let pinned = active_cells(self.selected_grid_size)
+            .zip(self.board_goals(&pool, cx))
+            .enumerate()
+            .filter(|(_, (idx, goal))| self.pinned_cells.contains(idx) && !goal.name.is_empty())
+            .map(|(board_idx, (_, goal))| (board_idx, goal))
+            .collect();
+
+        self.backend_handle.send(MessageToBackend::GenerateBoard {
+            pool,
+            options: GenerateOptions {
+                grid_size: self.selected_grid_size,
+                seed: self.seed_input.read(cx).value().trim().parse().ok(),
+                mode: self.mode,
+                line_difficulty: self.line_difficulty(cx),
+                avoid_recent: self.avoid_recent(&pool, cx),
+                pinned,
+            },
+        });
+    }
+
+    fn reroll(&mut self, scope: RerollScope, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        let Some(cell) = self
+            .context_cell
+            .and_then(|cell| board_cells.iter().position(|&c| c == cell))
+        else {
+            return;
+        };
+
+        let n = self.selected_grid_size.side();
+        let (y, x) = (cell / n, cell % n);
+
+        let cells: Vec<usize> = match scope {
+            RerollScope::Cell => vec![cell],
+            RerollScope::Row => (0..n).map(|x| y * n + x).collect(),
+            RerollScope::Column => (0..n).map(|y| y * n + x).collect(),
+            RerollScope::Diagonal if x == y => (0..n).map(|i| i * n + i).collect(),
+            RerollScope::AntiDiagonal if x + y == n - 1 => {
+                (0..n).map(|i| i * n + (n - 1 - i)).collect()
+            }
+            _ => {
+                window.push_notification(
+                    (
+                        NotificationType::Warning,
+                        "This cell isn't on that diagonal.",
+                    ),
+                    cx,
+                );
+                return;
+            }
+        };
+        self.reroll_cells(cells, pool, cx);
+    }
+
+    /// Rerolls the board indices `cells`, pinned ones stay.
+    fn reroll_cells(&self, cells: Vec<usize>, pool: GoalPool, cx: &Context<Self>) {
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        let cells = cells
+            .into_iter()
+            .filter(|&c| !self.pinned_cells.contains(&board_cells[c]))
+            .collect();
+
+        self.backend_handle.send(MessageToBackend::RerollCells {
+            board: self.board_goals(&pool, cx),
+            pool,
+            grid_size: self.selected_grid_size,
+            cells,
+        });
+    }
+
+    /// Moves the cursor one cell, or to the edge of the board with `to_edge`. The selection
+    /// grows with `extend` and collapses onto the cursor otherwise.
+    fn move_cursor(
+        &mut self,
+        direction: Direction,
+        to_edge: bool,
+        extend: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let last = self.selected_grid_size.side() - 1;
+        let Some(selection) = self.selection else {
+            self.selection = Some(CellSelection::at((0, 0)));
+            cx.notify();
+            return;
+        };
+
+        let (y, x) = selection.cursor;
+        let cursor = match (direction, to_edge) {
+            (Direction::Up, false) => (y.saturating_sub(1), x),
+            (Direction::Down, false) => ((y + 1).min(last), x),
+            (Direction::Left, false) => (y, x.saturating_sub(1)),
+            (Direction::Right, false) => (y, (x + 1).min(last)),
+            (Direction::Up, true) => (0, x),
+            (Direction::Down, true) => (last, x),
+            (Direction::Left, true) => (y, 0),
+            (Direction::Right, true) => (y, last),
+        };
+
+        self.selection = Some(if extend {
+            CellSelection {
+                anchor: selection.anchor,
+                cursor,
+            }
+        } else {
+            CellSelection::at(cursor)
+        });
+        cx.notify();
+    }
+
+    /// Selects the cell at `idx` into `cell_inputs`, or stretches the selection to it.
+    fn select_cell(&mut self, idx: usize, extend: bool, cx: &mut Context<Self>) {
+        let Some(cell) = active_cells(self.selected_grid_size).position(|c| c == idx) else {
+            return;
+        };
+        let n = self.selected_grid_size.side();
+        let cell = (cell / n, cell % n);
+
+        self.selection = Some(match self.selection {
+            Some(selection) if extend => CellSelection {
+                anchor: selection.anchor,
+                cursor: cell,
+            },
+            _ => CellSelection::at(cell),
+        });
+        cx.notify();
+    }
+
+    fn selected_cells_contain(&self, idx: usize) -> bool {
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        self.selected_cells().iter().any(|&c| board_cells[c] == idx)
+    }
+
+    /// Swaps two cells of `cell_inputs` with whether they're pinned and their category.
+    fn swap_cells(&mut self, a: usize, b: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if a == b {
+            return;
+        }
+
+        let text_a = self.cell_inputs[a].read(cx).value().to_string();
+        let text_b = self.cell_inputs[b].read(cx).value().to_string();
+        self.cell_inputs[a].update(cx, |is, cx| is.set_value(text_b, window, cx));
+        self.cell_inputs[b].update(cx, |is, cx| is.set_value(text_a, window, cx));
+
+        if self.pinned_cells.contains(&a) != self.pinned_cells.contains(&b) {
+            for idx in [a, b] {
+                if !self.pinned_cells.remove(&idx) {
+                    self.pinned_cells.insert(idx);
+                }
+            }
+        }
+
+        let category_a = self.cell_categories.remove(&a);
+        let category_b = self.cell_categories.remove(&b);
+        if let Some(category) = category_a {
+            self.cell_categories.insert(b, category);
+        }
+        if let Some(category) = category_b {
+            self.cell_categories.insert(a, category);
+        }
+
+        self.line_spread = None;
+        self.cell_paste = None;
+        cx.notify();
+    }
+
+    /// Moves every cell with its metadata. Exports read positions from the cells, so Lockout Live
+    /// forced positions follow along.
+    fn transform_board(
+        &mut self,
+        transform: BoardTransform,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        let texts: Vec<String> = board_cells
+            .iter()
+            .map(|&idx| self.cell_inputs[idx].read(cx).value().to_string())
+            .collect();
+
+        let mut pinned_cells = HashSet::new();
+        let mut cell_categories = HashMap::new();
+        for (cell, to) in transform
+            .positions(self.selected_grid_size)
+            .into_iter()
+            .enumerate()
+        {
+            let (from, to) = (board_cells[cell], board_cells[to]);
+
+            self.cell_inputs[to].update(cx, |is, cx| is.set_value(texts[cell].clone(), window, cx));
+            if self.pinned_cells.contains(&from) {
+                pinned_cells.insert(to);
+            }
+            if let Some(category) = self.cell_categories.get(&from) {
+                cell_categories.insert(to, category.clone());
+            }
+        }
+
+        self.pinned_cells = pinned_cells;
+        self.cell_categories = cell_categories;
+        self.selection = None;
+        self.cell_paste = None;
+        self.context_cell = None;
+        cx.notify();
+    }
+
+    /// Board indices of the selection, row-major.
+    fn selected_cells(&self) -> Vec<usize> {
+        self.selection
+            .map(|s| s.cells(self.selected_grid_size))
+            .unwrap_or_default()
+    }
+
+    fn edit_cell(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some((y, x)) = self.selection.map(|s| s.cursor) else {
+            return;
+        };
+        let n = self.selected_grid_size.side();
+
+        if let Some(idx) = active_cells(self.selected_grid_size).nth(y * n + x) {
+            self.cell_inputs[idx].focus_handle(cx).focus(window);
+        }
+    }
+
+    fn clear_selection(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        for cell in self.selected_cells() {
+            self.cell_inputs[board_cells[cell]].update(cx, |is, cx| is.set_value("", window, cx));
+        }
+        self.line_spread = None;
+        cx.notify();
+    }
+
+    /// Copies the selection as tab separated rows, the way spreadsheets and the multi-cell paste
+    /// read it.
+    fn copy_selection(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection else {
+            return;
+        };
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        let cells = selection.cells(self.selected_grid_size);
+        if cells.is_empty() {
+            return;
+        }
+
+        let rows: Vec<Vec<String>> = cells
+            .chunks(cells.len() / selection.rows(self.selected_grid_size))
+            .map(|row| {
+                row.iter()
+                    .map(|&c| {
+                        self.cell_inputs[board_cells[c]]
+                            .read(cx)
+                            .value()
+                            .to_string()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        cx.write_to_clipboard(ClipboardItem::new_string(table::write(
+            &rows,
+            Delimiter::Tab,
+        )));
+        window.push_notification(
+            (
+                NotificationType::Success,
+                format!("Copied {} cells.", cells.len()),
+            ),
+            cx,
+        );
+    }
+
+    fn reroll_selection(&self, cx: &mut Context<Self>) {
+        let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+            return;
+        };
+
+        self.reroll_cells(self.selected_cells(), pool, cx);
+    }
+
+    /// Puts the category from `category_input` on every selected cell, an empty one takes the
+    /// hand-set category off again.
+    fn set_selection_category(&mut self, cx: &mut Context<Self>) {
+        let category = self.category_input.read(cx).value().trim().to_string();
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+
+        for cell in self.selected_cells() {
+            if category.is_empty() {
+                self.cell_categories.remove(&board_cells[cell]);
+            } else {
+                self.cell_categories
+                    .insert(board_cells[cell], category.clone());
+            }
+        }
+        cx.notify();
+    }
+
+    /// `goal` with the category set by hand on the cell at `idx` put first.
+    fn categorized(&self, idx: usize, mut goal: Goal) -> Goal {
+        if let Some(category) = self.cell_categories.get(&idx) {
+            goal.categories.retain(|c| c != category);
+            goal.categories.insert(0, category.clone());
+        }
+
+        goal
+    }
+
+    /// Current board cells row-major, matched to pool goals by name where possible.
+    fn board_goals(&self, pool: &GoalPool, cx: &Context<Self>) -> Vec<Goal> {
+        active_cells(self.selected_grid_size)
+            .map(|idx| {
+                let value = self.cell_inputs[idx].read(cx).value().trim().to_string();
+                let goal = pool.resolve(&value).unwrap_or(Goal {
+                    name: value,
+                    ..Default::default()
+                });
+
+                self.categorized(idx, goal)
+            })
+            .collect()
+    }
+
+    /// Last generated board, as long as the cells haven't been edited since.
+    fn current_generated<'a>(&self, cx: &'a Context<Self>) -> Option<&'a GeneratedBoard> {
+        let board = self.generated_board.read(cx).board.as_ref()?;
+        if board.grid_size != self.selected_grid_size {
+            return None;
+        }
+
+        active_cells(self.selected_grid_size)
+            .zip(board.goals.iter())
+            .all(|(idx, goal)| goal.name.trim() == self.cell_inputs[idx].read(cx).value().trim())
+            .then_some(board)
+    }
+
+    /// Cells with the first category of their goal, when `categories` is set.
+    fn board_image(&self, categories: bool, cx: &Context<Self>) -> BoardImage {
+        let pool = self.goal_pool.read(cx).pool.as_ref();
+        let cells = active_cells(self.selected_grid_size)
+            .map(|idx| {
+                let text = self.cell_inputs[idx].read(cx).value().trim().to_string();
+                let category = self.cell_categories.get(&idx).cloned().or_else(|| {
+                    pool.and_then(|p| p.resolve(&text))
+                        .and_then(|g| g.categories.first().cloned())
+                });
+                let category = category.filter(|_| categories);
+
+                ImageCell { text, category }
+            })
+            .collect();
+
+        BoardImage {
+            title: pool.map(|p| p.game_name.clone()).unwrap_or_default(),
+            grid_size: self.selected_grid_size,
+            cells,
+        }
+    }
+
+    /// The board as a project. Cells keep the goal and notes of the opened project where the
+    /// pool doesn't know them, seed and options only while the board is unedited.
+    fn project(&self, cx: &Context<Self>) -> BingoProject {
+        let goal_pool = self.goal_pool.read(cx);
+        let loaded = self.generated_board.read(cx).project.as_ref();
+
+        let cells: Vec<ProjectCell> = active_cells(self.selected_grid_size)
+            .map(|idx| {
+                let text = self.cell_inputs[idx].read(cx).value().trim().to_string();
+                let previous =
+                    loaded.and_then(|p| p.cells.iter().find(|c| c.goal.name.trim() == text));
+                let goal = goal_pool
+                    .pool
+                    .as_ref()
+                    .and_then(|p| p.resolve(&text))
+                    .or_else(|| previous.map(|c| c.goal.clone()))
+                    .unwrap_or(Goal {
+                        name: text,
+                        ..Default::default()
+                    });
+
+                ProjectCell {
+                    goal: self.categorized(idx, goal),
+                    pinned: self.pinned_cells.contains(&idx),
+                    notes: previous.map(|c| c.notes.clone()).unwrap_or_default(),
+                }
+            })
+            .collect();
+
+        let mut project = BingoProject {
+            game_name: goal_pool
+                .pool
+                .as_ref()
+                .map(|p| p.game_name.clone())
+                .or_else(|| loaded.map(|p| p.game_name.clone()))
+                .unwrap_or_default(),
+            grid_size: self.selected_grid_size,
+            lockout_limits: goal_pool
+                .pool
+                .as_ref()
+                .map(LockoutLimits::from_pool)
+                .or_else(|| loaded.map(|p| p.lockout_limits.clone()))
+                .unwrap_or_default(),
+            pool_path: match &goal_pool.pool {
+                Some(_) => goal_pool.path.clone(),
+                None => loaded.and_then(|p| p.pool_path.clone()),
+            },
+            notes: self.notes_input.read(cx).value().to_string(),
+            cells,
+            ..Default::default()
+        };
+
+        if let Some(board) = self.current_generated(cx) {
+            project.seed = Some(board.seed);
+            project.mode = board.mode;
+            project.rerolled = board.rerolled;
+            project.constraints = BoardConstraints {
+                line_difficulty: board.line_difficulty,
+                pinned: board.pinned.clone(),
+                ..goal_pool
+                    .pool
+                    .as_ref()
+                    .map(BoardConstraints::from_pool)
+                    .unwrap_or_default()
+            };
+        } else if let Some(loaded) = loaded
+            .filter(|p| p.grid_size == project.grid_size && p.cell_texts() == project.cell_texts())
+        {
+            project.seed = loaded.seed;
+            project.mode = loaded.mode;
+            project.rerolled = loaded.rerolled;
+            project.constraints = loaded.constraints.clone();
         }
+
+        project
+    }
+
+    /// The board as a project when it fits `format`, warns about what doesn't otherwise.
+    fn export_project(
+        &self,
+        format: ExportFormat,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Option<BingoProject> {
+        let project = self.project(cx);
+
+        let warning = match format {
+            ExportFormat::BingoSync if project.grid_size > GridSize::Size5 => {
+                Some("Bingosync boards can't be larger than 5x5.")
+            }
+            ExportFormat::LockoutLive if project.cells.iter().any(|c| c.goal.name.len() > 60) => {
+                Some("Lockout Live can't have a task text longer than 60 characters.")
+            }
+            _ => None,
+        };
+
+        if let Some(warning) = warning {
+            window.push_notification((NotificationType::Warning, warning), cx);
+            return None;
+        }
+
+        Some(project)
+    }
+
+    fn open_project(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Open Project", |path| {
+            MessageToBackend::OpenProject { path }
+        });
+    }
+
+    /// Saves over the opened project, or as a new one when `save_as` is set or there's none.
+    fn save_project(&self, save_as: bool, cx: &Context<Self>) {
+        let path = self
+            .generated_board
+            .read(cx)
+            .project_path
+            .clone()
+            .filter(|_| !save_as);
+
+        self.backend_handle.send(MessageToBackend::SaveProject {
+            path,
+            project: self.project(cx),
+        });
+    }
+
+    fn export_metadata(&self, cx: &Context<Self>) -> Option<ExportMetadata> {
+        if !self.write_sidecar {
+            return None;
+        }
+
+        let goal_pool = self.goal_pool.read(cx);
+        let mut metadata = ExportMetadata {
+            pool_path: goal_pool.path.clone(),
+            grid_size: self.selected_grid_size,
+            ..Default::default()
+        };
+
+        if let Some(board) = self.current_generated(cx) {
+            metadata.seed = Some(board.seed);
+            metadata.mode = board.mode;
+            metadata.rerolled = board.rerolled;
+            metadata.avoided_recent = board.avoided_recent;
+            metadata.constraints = BoardConstraints {
+                line_difficulty: board.line_difficulty,
+                pinned: board.pinned.clone(),
+                ..goal_pool
+                    .pool
+                    .as_ref()
+                    .map(BoardConstraints::from_pool)
+                    .unwrap_or_default()
+            };
+        }
+
+        Some(metadata)
+    }
+
+    fn verify_export(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Verify Export", |path| {
+            MessageToBackend::VerifyExport { path }
+        });
+    }
+
+    /// Seed and options when the board is still exactly what was generated, the cell texts
+    /// otherwise.
+    fn board_code(&self, cx: &Context<Self>) -> BoardCode {
+        let cells: Vec<String> = active_cells(self.selected_grid_size)
+            .map(|idx| self.cell_inputs[idx].read(cx).value().trim().to_string())
+            .collect();
+
+        if let Some(pool) = &self.goal_pool.read(cx).pool
+            && let Some(board) = self.current_generated(cx)
+            && board.reproducible()
+        {
+            return BoardCode::generated(pool, board);
+        }
+
+        BoardCode::Custom {
+            grid_size: self.selected_grid_size,
+            cells,
+        }
+    }
+
+    fn paste_code(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let text = cx
+            .read_from_clipboard()
+            .and_then(|item| item.text())
+            .unwrap_or_default();
+
+        let board_code = match BoardCode::decode(&text) {
+            Ok(board_code) => board_code,
+            Err(e) => {
+                window.push_notification((NotificationType::Error, e.to_string()), cx);
+                return;
+            }
+        };
+
+        match board_code {
+            BoardCode::Generated {
+                pool_hash,
+                seed,
+                mode,
+                line_difficulty,
+                ..
+            } => {
+                let Some(pool) = self.goal_pool.read(cx).pool.clone() else {
+                    window.push_notification(
+                        (
+                            NotificationType::Warning,
+                            "Open the goal pool this board was generated from first.",
+                        ),
+                        cx,
+                    );
+                    return;
+                };
+
+                if code::pool_hash(&pool) != pool_hash {
+                    window.push_notification(
+                        (
+                            NotificationType::Warning,
+                            "This board was generated from a different goal pool.",
+                        ),
+                        cx,
+                    );
+                    return;
+                }
+
+                self.mode = mode;
+                self.pinned_cells.clear();
+                self.seed_input
+                    .update(cx, |is, cx| is.set_value(seed.to_string(), window, cx));
+                let (target, tolerance) = line_difficulty
+                    .map(|t| (t.target.to_string(), t.tolerance.to_string()))
+                    .unwrap_or_default();
+                self.target_input
+                    .update(cx, |is, cx| is.set_value(target, window, cx));
+                self.tolerance_input
+                    .update(cx, |is, cx| is.set_value(tolerance, window, cx));
+
+                if let Some(options) = board_code.options() {
+                    self.backend_handle
+                        .send(MessageToBackend::GenerateBoard { pool, options });
+                }
+            }
+            BoardCode::Custom { grid_size, cells } => {
+                self.set_cells(grid_size, cells, window, cx);
+            }
+        }
+    }
+
+    /// Replaces the board with hand-made cell texts, row-major.
+    fn set_cells(
+        &mut self,
+        grid_size: GridSize,
+        cells: Vec<String>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.selected_grid_size = grid_size;
+        self.line_spread = None;
+        self.pinned_cells.clear();
+        self.cell_paste = None;
+        self.selection = None;
+        self.cell_categories.clear();
+
+        self.cell_inputs
+            .iter()
+            .for_each(|e| e.update(cx, |is, cx| is.set_value("", window, cx)));
+        for (idx, cell) in active_cells(grid_size).zip(cells) {
+            self.cell_inputs[idx].update(cx, |is, cx| is.set_value(cell, window, cx));
+        }
+    }
+
+    /// Previews a tab separated block from the clipboard pasted into the cell at `idx`, a single
+    /// value is left to the cell input.
+    fn paste_cells(&mut self, idx: usize, cx: &mut Context<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+        if !text.trim_end().contains(['\t', '\n']) {
+            return;
+        }
+        let Ok(rows) = table::parse(&text, Delimiter::Tab) else {
+            return;
+        };
+        let Some(origin) = active_cells(self.selected_grid_size).position(|c| c == idx) else {
+            return;
+        };
+
+        let paste = CellPaste {
+            origin,
+            rows: rows
+                .into_iter()
+                .map(|row| row.iter().map(|v| v.trim().to_string()).collect())
+                .collect(),
+        };
+        if paste.value_count() < 2 {
+            return;
+        }
+
+        cx.stop_propagation();
+        self.cell_paste = Some(paste);
+        cx.notify();
+    }
+
+    /// Writes the previewed paste from `origin`, a different `grid_size` starts a new board with
+    /// just the pasted values.
+    fn apply_paste(
+        &mut self,
+        grid_size: GridSize,
+        origin: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(paste) = self.cell_paste.take() else {
+            return;
+        };
+
+        if grid_size != self.selected_grid_size {
+            let mut cells = vec![String::new(); grid_size.cell_count()];
+            for (board_idx, value) in paste.targets(grid_size, origin) {
+                cells[board_idx] = value;
+            }
+            self.set_cells(grid_size, cells, window, cx);
+        } else {
+            let board_cells: Vec<usize> = active_cells(grid_size).collect();
+            for (board_idx, value) in paste.targets(grid_size, origin) {
+                self.cell_inputs[board_cells[board_idx]]
+                    .update(cx, |is, cx| is.set_value(value, window, cx));
+            }
+            self.line_spread = None;
+        }
+
+        cx.notify();
+    }
+
+    fn import_board_table(&self, cx: &mut Context<Self>) {
+        prompt_single_file(cx, &self.backend_handle, "Import Board", |path| {
+            MessageToBackend::ImportBoardTable { path }
+        });
+    }
+
+    fn save_board_table(&self, delimiter: Delimiter, cx: &Context<Self>) {
+        let cells = active_cells(self.selected_grid_size)
+            .map(|idx| self.cell_inputs[idx].read(cx).value().to_string())
+            .collect();
+
+        self.backend_handle
+            .send(MessageToBackend::CreateBoardTableFile {
+                grid_size: self.selected_grid_size,
+                cells,
+                delimiter,
+            });
+    }
+
+    fn line_difficulty(&self, cx: &Context<Self>) -> Option<DifficultyTarget> {
+        let target = self.target_input.read(cx).value().trim().parse().ok()?;
+        let tolerance = self
+            .tolerance_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .unwrap_or(0);
+
+        Some(DifficultyTarget { target, tolerance })
+    }
+
+    fn avoid_recent(&self, pool: &GoalPool, cx: &Context<Self>) -> Option<AvoidRecent> {
+        let mode = self.avoid_mode?;
+        let window = self
+            .recent_input
+            .read(cx)
+            .value()
+            .trim()
+            .parse()
+            .ok()
+            .map(|count| {
+                if self.recent_in_days {
+                    HistoryWindow::Days(count)
+                } else {
+                    HistoryWindow::Boards(count as usize)
+                }
+            });
+
+        Some(AvoidRecent {
+            mode,
+            uses: self.usage_history.read(cx).history.uses(pool, window),
+        })
+    }
+}
+
+impl GeneratorPage {
+    fn render_selection_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let count = self.selected_cells().len();
+        let no_pool = self.goal_pool.read(cx).pool.is_none();
+
+        h_flex()
+            .gap_2()
+            .child(Label::new(if count == 1 {
+                "1 cell selected".to_string()
+            } else {
+                format!("{count} cells selected")
+            }))
+            .child(
+                ButtonGroup::new("selection-actions")
+                    .outline()
+                    .compact()
+                    .children(
+                        izip!(
+                            [
+                                "clear-selection-btn",
+                                "copy-selection-btn",
+                                "cut-selection-btn",
+                                "reroll-selection-btn"
+                            ],
+                            ["Clear", "Copy", "Cut", "Reroll"],
+                            [false, false, false, no_pool],
+                        )
+                        .map(|(name, label, disabled)| {
+                            Button::new(name).label(label).disabled(disabled)
+                        }),
+                    )
+                    .on_click(cx.listener(|view, selected: &Vec<usize>, window, cx| {
+                        match selected.first() {
+                            Some(0) => view.clear_selection(window, cx),
+                            Some(1) => view.copy_selection(window, cx),
+                            Some(2) => {
+                                view.copy_selection(window, cx);
+                                view.clear_selection(window, cx);
+                            }
+                            Some(3) => view.reroll_selection(cx),
+                            _ => {}
+                        }
+                    })),
+            )
+            .child(Input::new(&self.category_input).small())
+            .child(
+                Button::new("set-category-btn")
+                    .outline()
+                    .compact()
+                    .label("Set Category")
+                    .on_click(cx.listener(|view, _, _, cx| view.set_selection_category(cx))),
+            )
+    }
+
+    fn render_paste_preview(&self, paste: &CellPaste, cx: &mut Context<Self>) -> impl IntoElement {
+        let grid_size = self.selected_grid_size;
+        let n = grid_size.side();
+        let (rows, columns) = paste.size();
+        let landed = paste.targets(grid_size, paste.origin).len();
+        let fits = landed == paste.value_count();
+        let fitting_size = paste.grid_size();
+
+        let message = if fits {
+            format!(
+                "Paste {rows}x{columns} block from row {}, column {}?",
+                paste.origin / n + 1,
+                paste.origin % n + 1
+            )
+        } else {
+            format!(
+                "{rows}x{columns} block doesn't fit from row {}, column {}, {} of {} values would be cut off.",
+                paste.origin / n + 1,
+                paste.origin % n + 1,
+                paste.value_count() - landed,
+                paste.value_count()
+            )
+        };
+        let origin = paste.origin;
+
+        v_flex()
+            .gap_2()
+            .p_2()
+            .border_1()
+            .border_color(green_600())
+            .rounded_md()
+            .child(Label::new(message))
+            .child(div().grid().grid_cols(columns as u16).gap_1().children(
+                paste.rows.iter().flat_map(|row| {
+                    (0..columns).map(|x| {
+                        div()
+                            .text_xs()
+                            .truncate()
+                            .child(row.get(x).cloned().unwrap_or_default())
+                    })
+                }),
+            ))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("apply-paste-btn")
+                            .outline()
+                            .compact()
+                            .label(if fits { "Paste" } else { "Paste Anyway" })
+                            .icon(Icon::new(Icon::empty()).path("icons/clipboard-paste.svg"))
+                            .on_click(cx.listener(move |view, _, window, cx| {
+                                view.apply_paste(grid_size, origin, window, cx)
+                            })),
+                    )
+                    .when_some(
+                        fitting_size.filter(|g| !fits && *g <= grid_size),
+                        |this, _| {
+                            this.child(
+                                Button::new("paste-top-left-btn")
+                                    .outline()
+                                    .compact()
+                                    .label("Paste at Top Left")
+                                    .on_click(cx.listener(move |view, _, window, cx| {
+                                        view.apply_paste(grid_size, 0, window, cx)
+                                    })),
+                            )
+                        },
+                    )
+                    .when_some(
+                        fitting_size.filter(|g| *g > grid_size),
+                        |this, fitting_size| {
+                            this.child(
+                                Button::new("paste-resize-btn")
+                                    .outline()
+                                    .compact()
+                                    .label(format!("Resize to {0}x{0}", fitting_size.side()))
+                                    .on_click(cx.listener(move |view, _, window, cx| {
+                                        view.apply_paste(fitting_size, 0, window, cx)
+                                    })),
+                            )
+                        },
+                    )
+                    .child(
+                        Button::new("cancel-paste-btn")
+                            .outline()
+                            .compact()
+                            .label("Cancel")
+                            .on_click(cx.listener(|view, _, _, cx| {
+                                view.cell_paste = None;
+                                cx.notify();
+                            })),
+                    ),
+            )
     }
 }
 
+/// Indices into the 9x9 input grid that make up the centered board, row-major.
+fn active_cells(grid_size: GridSize) -> impl Iterator<Item = usize> {
+    let border = (9 - grid_size as usize).div_euclid(2);
+    let even = (grid_size as usize + 1) % 2;
+
+    (0..9 * 9).filter(move |idx| {
+        let x = idx % 9;
+        let y = idx.div_euclid(9);
+
+        x >= border + even && x < 9 - border && y >= border + even && y < 9 - border
+    })
+}
+
 impl Focusable for GeneratorPage {
     fn focus_handle(&self, _: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -75,117 +1331,273 @@ impl Focusable for GeneratorPage {
 
 impl Render for GeneratorPage {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        if let Some(action) = self.page_action.as_mut() {
+        if let Some(action) = self.page_action.take() {
             if matches!(action, PageAction::Clear) {
                 self.cell_inputs
                     .iter()
                     .for_each(|e| e.update(cx, |is, cx| is.set_value("", window, cx)));
                 self.focus_handle.focus(window);
+                self.line_spread = None;
+                self.pinned_cells.clear();
+                self.cell_paste = None;
+                self.selection = None;
+                self.cell_categories.clear();
             }
 
-            if matches!(action, PageAction::SaveBingosync | PageAction::SaveLockout) {
-                let v: Vec<String> = self
-                    .cell_inputs
+            if matches!(action, PageAction::ApplyGenerated)
+                && let Some(board) = self.generated_board.read(cx).board.clone()
+            {
+                self.selected_grid_size = board.grid_size;
+                self.line_spread = Some(board.line_spread);
+                self.selection = None;
+                self.cell_paste = None;
+
+                self.cell_inputs
                     .iter()
-                    .enumerate()
-                    .filter_map(|(idx, i)| {
-                        let border = (9 - self.selected_grid_size as usize).div_euclid(2);
-                        let even = (self.selected_grid_size as usize + 1) % 2;
-                        let x = idx % 9;
-                        let y = idx.div_euclid(9);
+                    .for_each(|e| e.update(cx, |is, cx| is.set_value("", window, cx)));
+                for (idx, goal) in active_cells(board.grid_size).zip(board.goals.iter()) {
+                    self.cell_inputs[idx]
+                        .update(cx, |is, cx| is.set_value(goal.name.clone(), window, cx));
+                }
+                if !board.rerolled {
+                    self.seed_input.update(cx, |is, cx| {
+                        is.set_value(board.seed.to_string(), window, cx)
+                    });
+                }
+            }
 
-                        if x >= border + even
-                            && x < 9 - border
-                            && y >= border + even
-                            && y < 9 - border
-                        {
-                            Some(i.read(cx).value().to_string())
-                        } else {
-                            None
-                        }
-                    })
+            if matches!(action, PageAction::ApplyGenerated)
+                && let Some((grid_size, cells)) = self.generated_board.read(cx).imported.clone()
+            {
+                self.set_cells(grid_size, cells, window, cx);
+            }
+
+            if matches!(action, PageAction::ApplyGenerated)
+                && let Some(project) = self.generated_board.read(cx).project.clone()
+            {
+                self.set_cells(project.grid_size, project.cell_texts(), window, cx);
+                self.pinned_cells = active_cells(project.grid_size)
+                    .zip(project.cells.iter())
+                    .filter(|(_, cell)| cell.pinned)
+                    .map(|(idx, _)| idx)
                     .collect();
+                self.mode = project.mode;
 
-                match action {
-                    PageAction::SaveBingosync => {
-                        let mut start: Vec<String> = vec![];
-                        let mut fill: Vec<String> = vec![];
-                        let mut end: Vec<String> = vec![];
+                let seed = project
+                    .seed
+                    .filter(|_| !project.rerolled)
+                    .map(|s| s.to_string())
+                    .unwrap_or_default();
+                self.seed_input
+                    .update(cx, |is, cx| is.set_value(seed, window, cx));
+                let (target, tolerance) = project
+                    .constraints
+                    .line_difficulty
+                    .map(|t| (t.target.to_string(), t.tolerance.to_string()))
+                    .unwrap_or_default();
+                self.target_input
+                    .update(cx, |is, cx| is.set_value(target, window, cx));
+                self.tolerance_input
+                    .update(cx, |is, cx| is.set_value(tolerance, window, cx));
+                self.notes_input
+                    .update(cx, |is, cx| is.set_value(project.notes, window, cx));
+            }
 
-                        let mut res: Vec<String> = vec![];
+            if matches!(action, PageAction::CopyCode) {
+                let board_code = self.board_code(cx);
+                let message = match board_code {
+                    BoardCode::Generated { .. } => "Copied board code.",
+                    BoardCode::Custom { .. } => "Copied board code with the cell texts.",
+                };
 
-                        match self.selected_grid_size {
-                            GridSize::Size3 => {
-                                start = vec![" ".to_string(); 6];
-                                fill = vec![" ".to_string(); 2];
-                                end = vec![" ".to_string(); 4];
-                            }
-                            GridSize::Size4 => {
-                                start = vec![" ".to_string(); 0];
-                                fill = vec![" ".to_string(); 1];
-                                end = vec![" ".to_string(); 5];
-                            }
-                            _ => {}
-                        }
+                cx.write_to_clipboard(ClipboardItem::new_string(board_code.encode()));
+                window.push_notification((NotificationType::Success, message), cx);
+            }
 
-                        res.extend(start);
+            if matches!(action, PageAction::PasteCode) {
+                self.paste_code(window, cx);
+            }
 
-                        for chunk in v.chunks_exact(self.selected_grid_size as usize) {
-                            res.extend(chunk.to_vec());
-                            res.extend(fill.clone());
-                        }
+            if let PageAction::SaveImage(format) = action {
+                self.backend_handle
+                    .send(MessageToBackend::CreateBoardImage {
+                        image: self.board_image(self.category_colors, cx),
+                        format,
+                    });
+            }
 
-                        res.extend(end);
+            if matches!(action, PageAction::SaveHtml) {
+                self.backend_handle.send(MessageToBackend::CreateHtmlBoard {
+                    board: self.board_image(true, cx),
+                    spoiler: self.html_spoiler,
+                });
+            }
+
+            if matches!(action, PageAction::SaveBingosync)
+                && let Some(project) = self.export_project(ExportFormat::BingoSync, window, cx)
+            {
+                self.backend_handle
+                    .send(MessageToBackend::CreateBingoSyncFile {
+                        data: project.bingo_sync_cards(),
+                        metadata: self.export_metadata(cx),
+                    });
+            }
+
+            if matches!(action, PageAction::SaveLockout)
+                && let Some(project) = self.export_project(ExportFormat::LockoutLive, window, cx)
+            {
+                let data = project.lockout_board();
 
-                        let data = res
-                            .iter()
-                            .map(|v| BingoSyncCard { name: v.to_owned() })
-                            .collect::<Vec<BingoSyncCard>>();
+                info!("{:?}", data);
 
-                        self.backend_handle
-                            .send(MessageToBackend::CreateBingoSyncFile { data });
+                self.backend_handle
+                    .send(MessageToBackend::CreateLockoutLiveFile {
+                        data,
+                        metadata: self.export_metadata(cx),
+                    });
+            }
+
+            if let PageAction::CopyJson(format) = action
+                && let Some(project) = self.export_project(format, window, cx)
+            {
+                let (json, message) = match format {
+                    ExportFormat::BingoSync => (
+                        serde_json::to_string_pretty(&project.bingo_sync_cards()),
+                        "Copied Bingosync JSON.",
+                    ),
+                    ExportFormat::LockoutLive => (
+                        serde_json::to_string_pretty(&project.lockout_board()),
+                        "Copied Lockout Live JSON.",
+                    ),
+                };
+
+                match json {
+                    Ok(json) => {
+                        cx.write_to_clipboard(ClipboardItem::new_string(json));
+                        window.push_notification((NotificationType::Success, message), cx);
                     }
-                    PageAction::SaveLockout => {
-                        let objectives = v
-                            .iter()
-                            .enumerate()
-                            .map(|(idx, v)| LockoutLiveCard::new(v.to_owned(), vec![idx + 1]))
-                            .collect::<Vec<LockoutLiveCard>>();
-
-                        if objectives.iter().any(|v| v.goal.len() > 60) {
-                            window.push_notification(
-                                (
-                                    NotificationType::Warning,
-                                    "Lockout Live can't have a task text longer than 60 characters.",
-                                ),
-                                cx,
-                            );
-                        } else {
-                            let data = LockoutLiveBoard {
-                                schema_version: 3,
-                                schema_mode: "relaxed".to_owned(),
-                                game_name: "None".to_string(),
-                                tag_names: Default::default(),
-                                objectives,
-                                limits: HashMap::from([
-                                    ("board".to_string(), HashMap::default()),
-                                    ("line".to_string(), HashMap::default()),
-                                ]),
-                            };
-
-                            info!("{:?}", data);
-
-                            self.backend_handle
-                                .send(MessageToBackend::CreateLockoutLiveFile { data });
-                        }
+                    Err(e) => {
+                        window.push_notification((NotificationType::Error, e.to_string()), cx)
                     }
-                    _ => unreachable!(),
                 }
             }
-            self.page_action = None;
         }
 
+        let board_cells: Vec<usize> = active_cells(self.selected_grid_size).collect();
+        let violations = match &self.goal_pool.read(cx).pool {
+            Some(pool) => {
+                let names: Vec<String> = board_cells
+                    .iter()
+                    .map(|&idx| self.cell_inputs[idx].read(cx).value().to_string())
+                    .collect();
+                rules::check_board(pool, self.selected_grid_size, &names)
+            }
+            None => Vec::new(),
+        };
+        let flagged_cells: HashSet<usize> = violations
+            .iter()
+            .flat_map(|v| v.cells.iter().map(|&c| board_cells[c]))
+            .collect();
+        let selected_cells: HashSet<usize> = self
+            .selected_cells()
+            .into_iter()
+            .map(|c| board_cells[c])
+            .collect();
+        let paste_targets: HashSet<usize> = self
+            .cell_paste
+            .iter()
+            .flat_map(|p| p.targets(self.selected_grid_size, p.origin))
+            .map(|(c, _)| board_cells[c])
+            .collect();
+
         v_flex()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|view, _: &RerollCell, window, cx| {
+                view.reroll(RerollScope::Cell, window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &RerollRow, window, cx| {
+                view.reroll(RerollScope::Row, window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &RerollColumn, window, cx| {
+                view.reroll(RerollScope::Column, window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &RerollDiagonal, window, cx| {
+                view.reroll(RerollScope::Diagonal, window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &RerollAntiDiagonal, window, cx| {
+                view.reroll(RerollScope::AntiDiagonal, window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveUp, _, cx| {
+                view.move_cursor(Direction::Up, false, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveDown, _, cx| {
+                view.move_cursor(Direction::Down, false, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveLeft, _, cx| {
+                view.move_cursor(Direction::Left, false, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveRight, _, cx| {
+                view.move_cursor(Direction::Right, false, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveToTop, _, cx| {
+                view.move_cursor(Direction::Up, true, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveToBottom, _, cx| {
+                view.move_cursor(Direction::Down, true, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveToLeftEdge, _, cx| {
+                view.move_cursor(Direction::Left, true, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &MoveToRightEdge, _, cx| {
+                view.move_cursor(Direction::Right, true, false, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectUp, _, cx| {
+                view.move_cursor(Direction::Up, false, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectDown, _, cx| {
+                view.move_cursor(Direction::Down, false, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectLeft, _, cx| {
+                view.move_cursor(Direction::Left, false, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectRight, _, cx| {
+                view.move_cursor(Direction::Right, false, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectToTop, _, cx| {
+                view.move_cursor(Direction::Up, true, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectToBottom, _, cx| {
+                view.move_cursor(Direction::Down, true, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectToLeftEdge, _, cx| {
+                view.move_cursor(Direction::Left, true, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &SelectToRightEdge, _, cx| {
+                view.move_cursor(Direction::Right, true, true, cx)
+            }))
+            .on_action(cx.listener(|view, _: &EditCell, window, cx| {
+                view.edit_cell(window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &StopEditing, window, cx| {
+                view.grid_focus.focus(window);
+                cx.notify();
+            }))
+            .on_action(cx.listener(|view, _: &ClearSelection, window, cx| {
+                view.clear_selection(window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &CopySelection, window, cx| {
+                view.copy_selection(window, cx)
+            }))
+            .on_action(cx.listener(|view, _: &CutSelection, window, cx| {
+                view.copy_selection(window, cx);
+                view.clear_selection(window, cx);
+            }))
+            .on_action(cx.listener(|view, _: &RerollSelection, _, cx| {
+                view.reroll_selection(cx)
+            }))
+            .on_action(cx.listener(|view, _: &SetSelectionCategory, _, cx| {
+                view.set_selection_category(cx)
+            }))
             .gap_4()
             .p_4()
             .size_full()
@@ -229,6 +1641,8 @@ impl Render for GeneratorPage {
                                             if !(v + 3 == view.selected_grid_size as usize) {
                                                 // view.trigger_clear = true;
                                                 view.page_action = Some(PageAction::Clear);
+                                                view.selection = None;
+                                                view.cell_paste = None;
                                             }
                                             view.selected_grid_size =
                                                 GridSize::from_repr(v + 3).unwrap();
@@ -271,7 +1685,7 @@ impl Render for GeneratorPage {
                                                 ),
                                             ],
                                             [
-                                                true,
+                                                self.goal_pool.read(cx).pool.is_none(),
                                                 false,
                                                 self.selected_grid_size as usize > 5,
                                                 false,
@@ -294,7 +1708,7 @@ impl Render for GeneratorPage {
                                     .on_click(cx.listener(
                                         |view, selected: &Vec<usize>, _w, cx| {
                                             match selected.first() {
-                                                Some(0) => randomize_action(),
+                                                Some(0) => view.randomize(cx),
                                                 Some(1) => {
                                                     view.page_action = Some(PageAction::Clear)
                                                 }
@@ -315,11 +1729,479 @@ impl Render for GeneratorPage {
                                     )),
                             ),
                         ),
+                    )
+                    .child(
+                        field()
+                            .label("Seed")
+                            .child(Input::new(&self.seed_input).small()),
+                    )
+                    .child(
+                        field().col_start(2).label("Mode").child(
+                            h_flex()
+                                .gap_4()
+                                .child(
+                                    ButtonGroup::new("generate-mode")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                GenerateMode::iter(),
+                                                [
+                                                    "random-mode-btn",
+                                                    "balanced-mode-btn",
+                                                    "constrained-mode-btn",
+                                                ],
+                                                ["Random", "Balanced", "Constrained"],
+                                            )
+                                            .map(
+                                                |(mode, name, label)| {
+                                                    Button::new(name)
+                                                        .label(label)
+                                                        .selected(self.mode == mode)
+                                                },
+                                            ),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                if let Some(mode) = selected
+                                                    .first()
+                                                    .and_then(|&v| GenerateMode::iter().nth(v))
+                                                {
+                                                    view.mode = mode;
+                                                }
+
+                                                cx.notify();
+                                            },
+                                        )),
+                                )
+                                .when_some(self.line_spread, |this, spread| {
+                                    this.child(Label::new(format!(
+                                        "Line difficulty {}..{} (spread {})",
+                                        spread.min,
+                                        spread.max,
+                                        spread.spread()
+                                    )))
+                                }),
+                        ),
+                    )
+                    .child(
+                        field().label("Line Difficulty").child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    Input::new(&self.target_input)
+                                        .small()
+                                        .disabled(self.mode != GenerateMode::Constrained),
+                                )
+                                .child(
+                                    Input::new(&self.tolerance_input)
+                                        .small()
+                                        .disabled(self.mode != GenerateMode::Constrained),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Board Code").child(
+                            div().child(
+                                ButtonGroup::new("board-code")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            ["copy-code-btn", "paste-code-btn"],
+                                            ["Copy Code", "Paste Code"],
+                                            ["icons/copy.svg", "icons/clipboard-paste.svg"],
+                                        )
+                                        .map(
+                                            |(name, label, icon)| {
+                                                Button::new(name)
+                                                    .label(label)
+                                                    .icon(Icon::new(Icon::empty()).path(icon))
+                                            },
+                                        ),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        match selected.first() {
+                                            Some(0) => {
+                                                view.page_action = Some(PageAction::CopyCode)
+                                            }
+                                            Some(1) => {
+                                                view.page_action = Some(PageAction::PasteCode)
+                                            }
+                                            _ => return,
+                                        }
+
+                                        cx.notify();
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().label("Export Metadata").child(
+                            h_flex()
+                                .gap_4()
+                                .child(
+                                    Checkbox::new("write-sidecar-checkbox")
+                                        .label("Write sidecar")
+                                        .checked(self.write_sidecar)
+                                        .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                            view.write_sidecar = *checked;
+                                            cx.notify();
+                                        })),
+                                )
+                                .child(
+                                    Button::new("verify-export-btn")
+                                        .outline()
+                                        .compact()
+                                        .label("Verify Export")
+                                        .icon(
+                                            Icon::new(Icon::empty()).path("icons/circle-check.svg"),
+                                        )
+                                        .on_click(
+                                            cx.listener(|view, _, _, cx| view.verify_export(cx)),
+                                        ),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Recent Goals").child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    ButtonGroup::new("avoid-mode")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                [
+                                                    None,
+                                                    Some(AvoidMode::DownWeight),
+                                                    Some(AvoidMode::Exclude)
+                                                ],
+                                                [
+                                                    "avoid-off-btn",
+                                                    "avoid-down-weight-btn",
+                                                    "avoid-exclude-btn"
+                                                ],
+                                                ["Allow", "Down-weight", "Exclude"],
+                                            )
+                                            .map(
+                                                |(mode, name, label)| {
+                                                    Button::new(name)
+                                                        .label(label)
+                                                        .selected(self.avoid_mode == mode)
+                                                },
+                                            ),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                view.avoid_mode = match selected.first() {
+                                                    Some(1) => Some(AvoidMode::DownWeight),
+                                                    Some(2) => Some(AvoidMode::Exclude),
+                                                    _ => None,
+                                                };
+
+                                                cx.notify();
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    Input::new(&self.recent_input)
+                                        .small()
+                                        .disabled(self.avoid_mode.is_none()),
+                                )
+                                .child(
+                                    ButtonGroup::new("recent-window")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                [false, true],
+                                                ["recent-boards-btn", "recent-days-btn"],
+                                                ["Boards", "Days"],
+                                            )
+                                            .map(
+                                                |(in_days, name, label)| {
+                                                    Button::new(name)
+                                                        .label(label)
+                                                        .selected(self.recent_in_days == in_days)
+                                                        .disabled(self.avoid_mode.is_none())
+                                                },
+                                            ),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                view.recent_in_days = selected.first() == Some(&1);
+                                                cx.notify();
+                                            },
+                                        )),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().label("Image").child(
+                            h_flex()
+                                .gap_4()
+                                .child(
+                                    ButtonGroup::new("save-image")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                ["save-png-btn", "save-svg-btn"],
+                                                ["PNG", "SVG"],
+                                            )
+                                            .map(|(name, label)| {
+                                                Button::new(name).label(label).icon(
+                                                    Icon::new(Icon::empty())
+                                                        .path("icons/save.svg"),
+                                                )
+                                            }),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                let Some(format) = selected
+                                                    .first()
+                                                    .and_then(|&i| ImageFormat::iter().nth(i))
+                                                else {
+                                                    return;
+                                                };
+
+                                                view.page_action =
+                                                    Some(PageAction::SaveImage(format));
+                                                cx.notify();
+                                            },
+                                        )),
+                                )
+                                .child(
+                                    Checkbox::new("category-colors-checkbox")
+                                        .label("Category colors")
+                                        .checked(self.category_colors)
+                                        .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                            view.category_colors = *checked;
+                                            cx.notify();
+                                        })),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("HTML Board").child(
+                            h_flex()
+                                .gap_4()
+                                .child(
+                                    Button::new("save-html-btn")
+                                        .outline()
+                                        .compact()
+                                        .label("HTML")
+                                        .icon(Icon::new(Icon::empty()).path("icons/save.svg"))
+                                        .on_click(cx.listener(|view, _, _, cx| {
+                                            view.page_action = Some(PageAction::SaveHtml);
+                                            cx.notify();
+                                        })),
+                                )
+                                .child(
+                                    Checkbox::new("html-spoiler-checkbox")
+                                        .label("Spoiler")
+                                        .checked(self.html_spoiler)
+                                        .on_click(cx.listener(|view, checked: &bool, _, cx| {
+                                            view.html_spoiler = *checked;
+                                            cx.notify();
+                                        })),
+                                ),
+                        ),
+                    )
+                    .child(
+                        field().label("Project").child(
+                            h_flex()
+                                .gap_2()
+                                .child(
+                                    ButtonGroup::new("board-project")
+                                        .outline()
+                                        .compact()
+                                        .children(
+                                            izip!(
+                                                [
+                                                    "open-project-btn",
+                                                    "save-project-btn",
+                                                    "save-project-as-btn"
+                                                ],
+                                                ["Open", "Save", "Save As"],
+                                                [
+                                                    None,
+                                                    Some("icons/save.svg"),
+                                                    Some("icons/save.svg")
+                                                ],
+                                            )
+                                            .map(
+                                                |(name, label, icon)| {
+                                                    let btn = Button::new(name).label(label);
+
+                                                    if let Some(i) = icon {
+                                                        return btn.icon(
+                                                            Icon::new(Icon::empty()).path(i),
+                                                        );
+                                                    }
+
+                                                    btn
+                                                },
+                                            ),
+                                        )
+                                        .on_click(cx.listener(
+                                            |view, selected: &Vec<usize>, _, cx| {
+                                                match selected.first() {
+                                                    Some(0) => view.open_project(cx),
+                                                    Some(1) => view.save_project(false, cx),
+                                                    Some(2) => view.save_project(true, cx),
+                                                    _ => {}
+                                                }
+                                            },
+                                        )),
+                                )
+                                .child(Input::new(&self.notes_input).small()),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Spreadsheet").child(
+                            div().child(
+                                ButtonGroup::new("board-table")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            [
+                                                "import-board-table-btn",
+                                                "save-board-csv-btn",
+                                                "save-board-tsv-btn"
+                                            ],
+                                            ["Import", "CSV", "TSV"],
+                                            [None, Some("icons/save.svg"), Some("icons/save.svg")],
+                                        )
+                                        .map(|(name, label, icon)| {
+                                            let btn = Button::new(name).label(label);
+
+                                            if let Some(i) = icon {
+                                                return btn.icon(Icon::new(Icon::empty()).path(i));
+                                            }
+
+                                            btn
+                                        }),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        match selected.first() {
+                                            Some(0) => view.import_board_table(cx),
+                                            Some(1) => view.save_board_table(Delimiter::Comma, cx),
+                                            Some(2) => view.save_board_table(Delimiter::Tab, cx),
+                                            _ => {}
+                                        }
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().label("Copy JSON").child(
+                            div().child(
+                                ButtonGroup::new("copy-json")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            ["copy-bingosync-btn", "copy-lockout-btn"],
+                                            ["Bingosync", "Lockout Live"],
+                                            [self.selected_grid_size > GridSize::Size5, false],
+                                        )
+                                        .map(|(name, label, disabled)| {
+                                            Button::new(name)
+                                                .label(label)
+                                                .icon(
+                                                    Icon::new(Icon::empty())
+                                                        .path("icons/copy.svg"),
+                                                )
+                                                .disabled(disabled)
+                                        }),
+                                    )
+                                    .on_click(cx.listener(|view, selected: &Vec<usize>, _, cx| {
+                                        let Some(format) = selected
+                                            .first()
+                                            .and_then(|&i| ExportFormat::iter().nth(i))
+                                        else {
+                                            return;
+                                        };
+
+                                        view.page_action = Some(PageAction::CopyJson(format));
+                                        cx.notify();
+                                    })),
+                            ),
+                        ),
+                    )
+                    .child(
+                        field().col_start(2).label("Transform").child(
+                            div().child(
+                                ButtonGroup::new("board-transform")
+                                    .outline()
+                                    .compact()
+                                    .children(
+                                        izip!(
+                                            [
+                                                "rotate-left-btn",
+                                                "rotate-right-btn",
+                                                "mirror-btn",
+                                                "flip-btn",
+                                                "transpose-btn",
+                                                "shuffle-lines-btn"
+                                            ],
+                                            [
+                                                "Rotate Left",
+                                                "Rotate Right",
+                                                "Mirror",
+                                                "Flip",
+                                                "Transpose",
+                                                "Shuffle Lines"
+                                            ],
+                                        )
+                                        .map(|(name, label)| Button::new(name).label(label)),
+                                    )
+                                    .on_click(cx.listener(
+                                        |view, selected: &Vec<usize>, window, cx| {
+                                            let transform = match selected.first() {
+                                                Some(0) => BoardTransform::RotateLeft,
+                                                Some(1) => BoardTransform::RotateRight,
+                                                Some(2) => BoardTransform::Mirror,
+                                                Some(3) => BoardTransform::Flip,
+                                                Some(4) => BoardTransform::Transpose,
+                                                Some(5) => BoardTransform::Shuffle,
+                                                _ => return,
+                                            };
+
+                                            view.transform_board(transform, window, cx);
+                                        },
+                                    )),
+                            ),
+                        ),
                     ),
             )
             .child(Divider::horizontal().gap_4())
+            .when(!violations.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .gap_1()
+                        .children(violations.iter().map(|v| Label::new(v.to_string()))),
+                )
+            })
+            .when_some(self.cell_paste.as_ref(), |this, paste| {
+                this.child(self.render_paste_preview(paste, cx))
+            })
+            .when(self.selection.is_some(), |this| {
+                this.child(self.render_selection_bar(cx))
+            })
             .child(
                 div()
+                    .absolute()
+                    .key_context(NAVIGATION_CONTEXT)
+                    .track_focus(&self.grid_focus),
+            )
+            .child(
+                div()
+                    .key_context(GRID_CONTEXT)
                     .gap_2()
                     .grid()
                     .size_full()
@@ -331,30 +2213,131 @@ impl Render for GeneratorPage {
                         let x = idx % 9;
                         let y = idx.div_euclid(9);
 
-                        Input::new(i)
-                            .when(
-                                i.read(cx).value().to_string().len() > 60
-                                    && i.focus_handle(cx).is_focused(window),
-                                |this| this.border_color(red_600()),
-                            )
-                            .when(
-                                i.read(cx).value().to_string().len() > 60
-                                    && !i.focus_handle(cx).is_focused(window),
-                                |this| this.border_color(red_800()),
-                            )
-                            .with_size(px(24.))
-                            .disabled(
-                                !(x >= border + even
-                                    && x < 9 - border
-                                    && y >= border + even
-                                    && y < 9 - border),
+                        let active = x >= border + even
+                            && x < 9 - border
+                            && y >= border + even
+                            && y < 9 - border;
+                        let pinned = self.pinned_cells.contains(&idx);
+
+                        div()
+                            .id(("board-cell", idx))
+                            .relative()
+                            .border_2()
+                            .rounded_md()
+                            .border_color(if selected_cells.contains(&idx) {
+                                purple_500()
+                            } else {
+                                transparent_black()
+                            })
+                            .when(active, |this| {
+                                this.drag_over::<DraggedCell>(|style, _, _, _| {
+                                    style.border_color(green_600())
+                                })
+                                .on_drop(cx.listener(move |view, cell: &DraggedCell, window, cx| {
+                                    view.swap_cells(cell.idx, idx, window, cx)
+                                }))
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |view, event: &MouseDownEvent, _, cx| {
+                                        view.select_cell(idx, event.modifiers.shift, cx)
+                                    }),
+                                )
+                                .on_mouse_down(
+                                    MouseButton::Right,
+                                    cx.listener(move |view, _, window, cx| {
+                                        view.context_cell = Some(idx);
+                                        if !view.selected_cells_contain(idx) {
+                                            view.select_cell(idx, false, cx);
+                                        }
+                                        view.grid_focus.focus(window);
+                                    }),
+                                )
+                                .capture_action(cx.listener(move |view, _: &Paste, _, cx| {
+                                    view.paste_cells(idx, cx)
+                                }))
+                            })
+                            .child(
+                                Input::new(i)
+                                    .when(pinned, |this| this.border_color(blue_600()))
+                                    .when(paste_targets.contains(&idx), |this| {
+                                        this.border_color(green_600())
+                                    })
+                                    .when(flagged_cells.contains(&idx), |this| {
+                                        this.border_color(yellow_600())
+                                    })
+                                    .when(
+                                        i.read(cx).value().to_string().len() > 60
+                                            && i.focus_handle(cx).is_focused(window),
+                                        |this| this.border_color(red_600()),
+                                    )
+                                    .when(
+                                        i.read(cx).value().to_string().len() > 60
+                                            && !i.focus_handle(cx).is_focused(window),
+                                        |this| this.border_color(red_800()),
+                                    )
+                                    .with_size(px(24.))
+                                    .disabled(!active)
+                                    .border_4(),
                             )
-                            .border_4()
+                            .when(active, |this| {
+                                this.child(
+                                    div()
+                                        .id(("drag-cell", idx))
+                                        .absolute()
+                                        .top_1()
+                                        .left_1()
+                                        .cursor_grab()
+                                        .child(
+                                            Icon::new(Icon::empty())
+                                                .path("icons/grip-vertical.svg")
+                                                .xsmall(),
+                                        )
+                                        .on_drag(
+                                            DraggedCell {
+                                                idx,
+                                                text: i.read(cx).value().to_string().into(),
+                                            },
+                                            |cell, _, _, cx| cx.new(|_| cell.clone()),
+                                        ),
+                                )
+                                .child(
+                                    div().absolute().top_1().right_1().child(
+                                        Button::new(("pin-cell-btn", idx))
+                                            .ghost()
+                                            .xsmall()
+                                            .selected(pinned)
+                                            .icon(Icon::new(Icon::empty()).path(if pinned {
+                                                "icons/lock.svg"
+                                            } else {
+                                                "icons/lock-open.svg"
+                                            }))
+                                            .on_click(cx.listener(move |view, _, _, cx| {
+                                                if !view.pinned_cells.remove(&idx) {
+                                                    view.pinned_cells.insert(idx);
+                                                }
+
+                                                cx.notify();
+                                            })),
+                                    ),
+                                )
+                            })
+                            .context_menu(move |menu, _, _| {
+                                if !active {
+                                    return menu;
+                                }
+
+                                menu.menu("Reroll Cell", Box::new(RerollCell))
+                                    .menu("Reroll Row", Box::new(RerollRow))
+                                    .menu("Reroll Column", Box::new(RerollColumn))
+                                    .menu("Reroll Diagonal", Box::new(RerollDiagonal))
+                                    .menu("Reroll Anti-Diagonal", Box::new(RerollAntiDiagonal))
+                                    .separator()
+                                    .menu("Reroll Selection", Box::new(RerollSelection))
+                                    .menu("Copy Selection", Box::new(CopySelection))
+                                    .menu("Cut Selection", Box::new(CutSelection))
+                                    .menu("Clear Selection", Box::new(ClearSelection))
+                            })
                     })),
             )
     }
 }
-
-fn randomize_action() {
-    log::info!("randomize_action");
-}
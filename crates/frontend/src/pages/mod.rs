@@ -1,2 +1,34 @@
+use std::path::PathBuf;
+
+use bridge::{handle::BackendHandle, message::MessageToBackend};
+use gpui::{Context, PathPromptOptions};
+
+pub mod bulk_page;
+pub mod caller_page;
 pub mod data_page;
 pub mod generator_page;
+
+/// Asks for a single file and sends the backend the message made from its path.
+pub fn prompt_single_file<T: 'static>(
+    cx: &mut Context<T>,
+    backend_handle: &BackendHandle,
+    prompt: &'static str,
+    message: impl FnOnce(PathBuf) -> MessageToBackend + 'static,
+) {
+    let paths = cx.prompt_for_paths(PathPromptOptions {
+        files: true,
+        directories: false,
+        multiple: false,
+        prompt: Some(prompt.into()),
+    });
+    let backend_handle = backend_handle.clone();
+
+    cx.spawn(async move |_, _| {
+        if let Ok(Ok(Some(mut paths))) = paths.await
+            && let Some(path) = paths.pop()
+        {
+            backend_handle.send(message(path));
+        }
+    })
+    .detach();
+}
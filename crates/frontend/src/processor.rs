@@ -1,10 +1,15 @@
-use bridge::{handle::BackendHandle, message::MessageToFrontend};
+use bridge::{
+    handle::BackendHandle,
+    message::{MessageToBackend, MessageToFrontend},
+};
 use gpui::{AnyWindowHandle, App, SharedString, Window};
 use gpui_component::{
     WindowExt,
     notification::{Notification, NotificationType},
 };
 
+use crate::entity::DataEntities;
+
 pub struct Processor {
     backend_handle: BackendHandle,
     main_window_handle: Option<AnyWindowHandle>,
@@ -55,7 +60,10 @@ impl Processor {
                     let MessageToFrontend::AddNotification {
                         notification_type,
                         message,
-                    } = message;
+                    } = message
+                    else {
+                        unreachable!()
+                    };
 
                     let notification_type = match notification_type {
                         bridge::message::NotificationType::Error => NotificationType::Error,
@@ -73,6 +81,98 @@ impl Processor {
                     window.push_notification(notification, cx);
                 });
             }
+            MessageToFrontend::GoalPoolLoaded { path, pool } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.goal_pool.update(cx, |goal_pool, cx| {
+                    goal_pool.path = path;
+                    goal_pool.pool = Some(*pool);
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::BoardGenerated { board } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.generated_board.update(cx, |generated_board, cx| {
+                    generated_board.board = Some(board);
+                    generated_board.imported = None;
+                    generated_board.project = None;
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::BoardImported { grid_size, cells } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.generated_board.update(cx, |generated_board, cx| {
+                    generated_board.board = None;
+                    generated_board.imported = Some((grid_size, cells));
+                    generated_board.project = None;
+                    generated_board.project_path = None;
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::ProjectOpened { path, project } => {
+                let entities = cx.global::<DataEntities>().clone();
+                if entities.goal_pool.read(cx).pool.is_none()
+                    && let Some(pool_path) = project.pool_path.clone().filter(|p| p.exists())
+                {
+                    self.backend_handle
+                        .send(MessageToBackend::LoadGoalPool { path: pool_path });
+                }
+                entities.generated_board.update(cx, |generated_board, cx| {
+                    generated_board.board = None;
+                    generated_board.imported = None;
+                    generated_board.project = Some(*project);
+                    generated_board.project_path = Some(path);
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::ProjectSaved { path } => {
+                let entities = cx.global::<DataEntities>().clone();
+                // No notify, that would apply the board again over any edits since saving.
+                entities.generated_board.update(cx, |generated_board, _| {
+                    generated_board.project_path = Some(path);
+                });
+            }
+            MessageToFrontend::BulkProgress { done, total } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.bulk_job.update(cx, |bulk_job, cx| {
+                    bulk_job.done = done;
+                    bulk_job.total = total;
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::TournamentPlanned { report } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.bulk_job.update(cx, |bulk_job, cx| {
+                    bulk_job.report = Some(report);
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::CallerGameLoaded {
+                path,
+                card_set,
+                game,
+            } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.caller.update(cx, |caller, cx| {
+                    caller.path = Some(path);
+                    caller.card_set = Some(*card_set);
+                    caller.game = Some(game);
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::UsageHistoryLoaded { history } => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.usage_history.update(cx, |usage_history, cx| {
+                    usage_history.history = history;
+                    cx.notify();
+                });
+            }
+            MessageToFrontend::BulkFinished => {
+                let entities = cx.global::<DataEntities>().clone();
+                entities.bulk_job.update(cx, |bulk_job, cx| {
+                    bulk_job.running = false;
+                    cx.notify();
+                });
+            }
         }
     }
 }
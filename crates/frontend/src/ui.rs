@@ -9,7 +9,10 @@ use gpui_component::{
     v_flex,
 };
 
-use crate::pages::{data_page::DataPage, generator_page::GeneratorPage};
+use crate::pages::{
+    bulk_page::BulkPage, caller_page::CallerPage, data_page::DataPage,
+    generator_page::GeneratorPage,
+};
 
 pub struct Ui {
     focus_handle: FocusHandle,
@@ -21,6 +24,8 @@ pub struct Ui {
 pub enum MainPage {
     Data(Entity<DataPage>),
     Generator(Entity<GeneratorPage>),
+    Bulk(Entity<BulkPage>),
+    Caller(Entity<CallerPage>),
 }
 
 impl MainPage {
@@ -28,6 +33,8 @@ impl MainPage {
         match self {
             MainPage::Data(entity) => entity.into_any_element(),
             MainPage::Generator(entity) => entity.into_any_element(),
+            MainPage::Bulk(entity) => entity.into_any_element(),
+            MainPage::Caller(entity) => entity.into_any_element(),
         }
     }
 
@@ -35,6 +42,8 @@ impl MainPage {
         match self {
             MainPage::Data(_) => PageType::Data,
             MainPage::Generator(_) => PageType::Generator,
+            MainPage::Bulk(_) => PageType::Bulk,
+            MainPage::Caller(_) => PageType::Caller,
         }
     }
 }
@@ -43,6 +52,8 @@ impl MainPage {
 pub enum PageType {
     Data,
     Generator,
+    Bulk,
+    Caller,
 }
 
 impl PageType {
@@ -53,10 +64,18 @@ impl PageType {
         cx: &mut App,
     ) -> MainPage {
         match self {
-            PageType::Data => MainPage::Data(cx.new(|cx| DataPage::new(window, cx))),
+            PageType::Data => {
+                MainPage::Data(cx.new(|cx| DataPage::new(backend_handle, window, cx)))
+            }
             PageType::Generator => {
                 MainPage::Generator(cx.new(|cx| GeneratorPage::new(backend_handle, window, cx)))
             }
+            PageType::Bulk => {
+                MainPage::Bulk(cx.new(|cx| BulkPage::new(backend_handle, window, cx)))
+            }
+            PageType::Caller => {
+                MainPage::Caller(cx.new(|cx| CallerPage::new(backend_handle, window, cx)))
+            }
         }
     }
 }
@@ -88,6 +107,8 @@ impl Render for Ui {
         let selected_index = match &self.page {
             MainPage::Generator(_) => 0,
             MainPage::Data(_) => 1,
+            MainPage::Bulk(_) => 2,
+            MainPage::Caller(_) => 3,
         };
 
         v_flex()
@@ -101,11 +122,15 @@ impl Render for Ui {
                     .prefix(div().w_4())
                     .selected_index(selected_index)
                     .child(Tab::new().label("Generator"))
-                    // .child(Tab::new().label("Data"))
+                    .child(Tab::new().label("Data"))
+                    .child(Tab::new().label("Bulk"))
+                    .child(Tab::new().label("Caller"))
                     .on_click(cx.listener(|page, idx, window, cx| {
                         let page_type = match *idx {
                             0 => PageType::Generator,
                             1 => PageType::Data,
+                            2 => PageType::Bulk,
+                            3 => PageType::Caller,
                             _ => {
                                 return;
                             }